@@ -1,4 +1,15 @@
-#!/bin/sh
+#!/bin/bash
+# Timings are appended to results/groth16.jsonl at the root of the repo, see ../results.sh
+CIRCUIT=RepeatedLessThan
+RESULTS=${BENCH_RESULTS_DIR:-$(cd ../.. && pwd)/results}/groth16.jsonl
+. ../results.sh
+
+# The three commands of the circuit specific set-up, timed together
+setup() {
+    snarkjs groth16 setup ../range.r1cs ../../pot/pot20_final.ptau range.zkey &&  \
+    snarkjs zkey contribute range.zkey range-1.zkey --name="1st Contributor Name" -v <<< 'jhcag7f23gr9fg4y38gfib43gfn348' &&  \
+    snarkjs zkey export verificationkey range-1.zkey verification_key.json
+}
 
 # circom range.circom --r1cs --wasm --json
 # cd range_js
@@ -15,30 +26,31 @@
 
 # Circuit compilation
 echo "______BENCHING CIRCUIT COMPILATION______"
-time circom range.circom --r1cs --wasm --json
+timed compile circom range.circom --r1cs --wasm --json
 
 # Witness generation
 cd range_js
 echo "______BENCHING WITNESS GENERATION______"
-time node generate_witness.js range.wasm ../input.json witness.wtns
+timed witness node generate_witness.js range.wasm ../input.json witness.wtns
 
 # Circuit specific setup
 echo "______BENCHING CIRCUIT SET-UP______"
-time (snarkjs groth16 setup ../range.r1cs ../../pot/pot20_final.ptau range.zkey &&  \
-snarkjs zkey contribute range.zkey range-1.zkey --name="1st Contributor Name" -v <<< 'jhcag7f23gr9fg4y38gfib43gfn348' &&  \
-snarkjs zkey export verificationkey range-1.zkey verification_key.json)
+timed setup setup
 
 # Proof generation
 echo "______BENCHING PROOF GENERATION______"
-time snarkjs groth16 prove range-1.zkey witness.wtns proof.json public.json
+timed prove snarkjs groth16 prove range-1.zkey witness.wtns proof.json public.json
 
 # Verify the Proof
 echo "______BENCHING PROOF VERIFICATION______"
-time snarkjs groth16 verify verification_key.json public.json proof.json
+timed verify snarkjs groth16 verify verification_key.json public.json proof.json
 
 
+# Record the timings against the k of the circuit, i.e. ceil(log2(#constraints))
+record_results $(log2_ceil $(constraints ../range.r1cs))
+
 ## Clean-up
 cd ..
 rm range.r1cs
 rm range_constraints.json
-rm -r range_js
\ No newline at end of file
+rm -r range_js
@@ -0,0 +1,68 @@
+# Helpers shared by the bench scripts to record their timings in results/groth16.jsonl, in the
+# format read by `cargo run --bin report` in the halo2 folder. Sourced by the scripts, which set
+# CIRCUIT (the name the halo2 benches use) and RESULTS (path of the results file).
+
+# Current time in milliseconds (macOS' date has no %N)
+now_ms() {
+    node -e 'console.log(Date.now())'
+}
+
+# ceil(log2(n)), the k of a circuit with n constraints
+log2_ceil() {
+    k=0
+    while [ $((1 << k)) -lt "$1" ]; do
+        k=$((k + 1))
+    done
+    echo $k
+}
+
+# Number of constraints of an .r1cs file
+constraints() {
+    snarkjs r1cs info "$1" | sed -n 's/.*# of Constraints: *\([0-9]*\).*/\1/p'
+}
+
+# Runs `timed <phase> <command...>`, printing the time the command took and keeping it in
+# PHASE_<phase>. Phases are the snake case names of halo2::results::Phase.
+timed() {
+    phase=$1
+    shift
+    start=$(now_ms)
+    "$@"
+    elapsed=$(($(now_ms) - start))
+    echo "$phase: ${elapsed}ms"
+    eval "PHASE_$phase=$elapsed"
+}
+
+# A Groth16 proof over BN254 is two G1 points and one G2 point, compressed as they are in the
+# halo2 proofs it is compared to. snarkjs' proof.json holds them as decimal strings instead.
+PROOF_BYTES=128
+
+# OS and architecture, named the way Rust's std::env::consts does
+machine() {
+    case "$(uname -s)" in
+        Darwin) os=macos ;;
+        *) os=$(uname -s | tr '[:upper:]' '[:lower:]') ;;
+    esac
+    case "$(uname -m)" in
+        arm64) arch=aarch64 ;;
+        *) arch=$(uname -m) ;;
+    esac
+    echo "{\"os\":\"$os\",\"arch\":\"$arch\",\"threads\":$(getconf _NPROCESSORS_ONLN)}"
+}
+
+# Appends the timings of every phase to RESULTS, for a circuit of 2^$1 constraints
+record_results() {
+    k=$1
+    machine=$(machine)
+    mkdir -p "$(dirname "$RESULTS")"
+    for phase in compile witness setup prove verify; do
+        eval "ms=\$PHASE_$phase"
+        if [ "$phase" = prove ]; then
+            size=",\"proof_bytes\":$PROOF_BYTES"
+        else
+            size=""
+        fi
+        echo "{\"system\":\"groth16\",\"circuit\":\"$CIRCUIT\",\"k\":$k,\"phase\":\"$phase\",\"mean_ms\":$ms$size,\"machine\":$machine}" >> "$RESULTS"
+    done
+    echo "Results appended to $RESULTS"
+}
@@ -0,0 +1,200 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+use halo2::{
+    cache::KeyCache,
+    ecdsa::EcdsaVerifyCircuit,
+    iszero::IsZeroCircuit,
+    prover::{instance_refs, prove_with_transcript, variant_name, verify_batch, MultiOpen},
+    range::RangeCircuit,
+    results::{self, Phase},
+    transcript::TranscriptHash,
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr, plonk::Circuit};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    time::Instant,
+};
+
+mod harness;
+use harness::{
+    k_range, kzg_params, measure_memory, multiopen_schemes, record_results, transcripts,
+    Measurements, SAMPLE_SIZE,
+};
+
+/// Environment variable holding the comma separated batch sizes to bench, e.g. `BATCH_SIZES=1,64`
+const BATCH_SIZES_VAR: &str = "BATCH_SIZES";
+
+/// Number of distinct proofs a batch cycles through. Proving dominates the time taken to set up
+/// the large batches, and verifying a proof twice costs the verifier as much as verifying two.
+const DISTINCT_PROOFS: usize = 8;
+
+/// Returns the batch sizes set in `BATCH_SIZES`, or 1, 8, 64 and 512 when it isn't set
+fn batch_sizes() -> Vec<usize> {
+    match env::var(BATCH_SIZES_VAR) {
+        Ok(sizes) => sizes
+            .split(',')
+            .map(|size| {
+                size.trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{} should only contain integers", BATCH_SIZES_VAR))
+            })
+            .collect(),
+        Err(_) => vec![1, 8, 64, 512],
+    }
+}
+
+/// Name batches of `size` proofs made with `scheme` and `transcript` are benched and recorded
+/// under
+fn batch_variant(scheme: MultiOpen, transcript: TranscriptHash, size: usize) -> String {
+    format!("{} batch of {}", variant_name(scheme, transcript), size)
+}
+
+/// Benches the verification of batches of proofs of the circuits built by `circuit`, for every
+/// size in `ks`, under the criterion group "`<name>` Batch Proof Verification".
+///
+/// Every batch is verified with one accumulator, see `halo2::prover::verify_batch`. The time
+/// recorded in the results is the amortised time per proof, that of the whole batch divided by
+/// its size, to be compared with the single proof verification of the other benches.
+fn bench_batch_verification<C, F>(
+    c: &mut Criterion,
+    name: &str,
+    ks: &[u32],
+    circuit: F,
+    instances: Vec<Vec<Fr>>,
+) where
+    C: Circuit<Fr>,
+    F: Fn(u32) -> C,
+{
+    let cache = KeyCache::from_env();
+    let schemes = multiopen_schemes();
+    let transcripts = transcripts();
+    let sizes = batch_sizes();
+    let instance_refs = instance_refs(&instances);
+    let instances: &[&[&[Fr]]] = &[instance_refs.as_slice()];
+    let mut measurements: BTreeMap<(MultiOpen, TranscriptHash, usize), Measurements> =
+        BTreeMap::new();
+
+    let mut batch_verification = c.benchmark_group(format!("{} Batch Proof Verification", name));
+    batch_verification.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let pk = cache
+            .pk(name, &params, &circuit(k))
+            .expect("keygen should not fail");
+        for (&scheme, &transcript) in schemes.iter().flat_map(|scheme| {
+            transcripts
+                .iter()
+                .map(move |transcript| (scheme, transcript))
+        }) {
+            let distinct = sizes
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or(0)
+                .min(DISTINCT_PROOFS);
+            let proofs: Vec<Vec<u8>> = (0..distinct)
+                .map(|_| {
+                    prove_with_transcript(
+                        scheme,
+                        transcript,
+                        &params,
+                        &pk,
+                        &[circuit(k)],
+                        instances,
+                    )
+                    .expect("proof generation should not fail")
+                })
+                .collect();
+
+            for &size in sizes.iter() {
+                let batch: Vec<(&[&[&[Fr]]], &[u8])> = proofs
+                    .iter()
+                    .cycle()
+                    .take(size)
+                    .map(|proof| (instances, proof.as_slice()))
+                    .collect();
+                let measurements = measurements.entry((scheme, transcript, size)).or_default();
+                // Memory of the whole batch, the accumulator doesn't grow with it
+                measurements.memory.insert(
+                    (k, Phase::Verify),
+                    measure_memory(|| {
+                        verify_batch(scheme, transcript, &params, pk.get_vk(), &batch)
+                            .expect("batch verification should not fail")
+                    }),
+                );
+                let timing = measurements.timings.entry((k, Phase::Verify)).or_default();
+                batch_verification.throughput(Throughput::Elements(size as u64));
+                let id = BenchmarkId::new(batch_variant(scheme, transcript, size), k);
+                batch_verification.bench_function(id, |b| {
+                    b.iter_custom(|iters| {
+                        let start = Instant::now();
+                        for _ in 0..iters {
+                            verify_batch(scheme, transcript, &params, pk.get_vk(), &batch)
+                                .expect("batch verification should not fail");
+                        }
+                        let elapsed = start.elapsed();
+                        // Every iteration verifies `size` proofs
+                        timing.record(elapsed, iters * size as u64);
+                        elapsed
+                    });
+                });
+            }
+        }
+    }
+    batch_verification.finish();
+
+    for ((scheme, transcript, size), measurements) in measurements {
+        record_results(
+            results::HALO2_RESULTS,
+            "halo2",
+            Some(&batch_variant(scheme, transcript, size)),
+            None,
+            name,
+            &measurements,
+        );
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable
+    let ks = k_range(&[10, 14]);
+
+    // Same circuits and inputs as `benches/iszero.rs`, `benches/range.rs` and `benches/ecdsa.rs`
+    let repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroCircuit::<Fr>::repeats_for_k(k)))
+        .collect();
+    bench_batch_verification(
+        c,
+        "IsZero",
+        &ks,
+        |k| IsZeroCircuit::new(Value::known(Fr::from(2)), repeats[&k]),
+        vec![],
+    );
+
+    let inputs = RangeCircuit::<Fr>::bench_inputs();
+    let range_repeats: HashMap<u32, u32> = ks
+        .iter()
+        .map(|&k| (k, RangeCircuit::<Fr>::bench_repeats_for_k(k)))
+        .collect();
+    bench_batch_verification(
+        c,
+        "Range",
+        &ks,
+        |k| RangeCircuit::new(inputs.clone(), range_repeats[&k]),
+        vec![vec![]],
+    );
+
+    // One signature verification takes between 2^17 and 2^18 rows, so smaller sizes set in
+    // `K_RANGE` are skipped
+    let ecdsa_ks: Vec<u32> = k_range(&[18]).into_iter().filter(|&k| k >= 18).collect();
+    let circuit = EcdsaVerifyCircuit::<Secp256k1, Fr>::random();
+    bench_batch_verification(c, "ECDSA", &ecdsa_ks, |_| circuit.clone(), vec![vec![]]);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
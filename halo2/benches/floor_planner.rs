@@ -0,0 +1,174 @@
+#[macro_use]
+extern crate criterion;
+use criterion::Criterion;
+
+use halo2::{
+    ecdsa::EcdsaVerifyCircuit,
+    iszero::{IsZeroCircuit, IsZeroPublicCircuit},
+    iszero_chip::IsZeroChipCircuit,
+    layout::WithFloorPlanner,
+    range::RangeCircuit,
+};
+use halo2_proofs::{
+    circuit::{floor_planner::V1, Value},
+    halo2curves::bn256::Fr as Fp,
+    plonk::Circuit,
+};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use std::collections::HashMap;
+use zkevm_circuits::keccak_circuit::{
+    keccak_bit::KeccakBitCircuit, keccak_packed::KeccakPackedCircuit,
+};
+
+mod harness;
+use harness::{bench_circuit_with_instances, k_range};
+
+/// Benches the circuits built by `circuit(k)`, laid out by `SimpleFloorPlanner`, as "`<name>`
+/// SimpleFloorPlanner", and the same circuits laid out by `V1` by `v1`, as "`<name>` V1".
+///
+/// Both lay out the same workload, the one `circuit(k)` fits to `2^k` rows with
+/// `SimpleFloorPlanner`, so the rows used recorded along with verifier key generation show how much
+/// of the table `V1` saves.
+fn bench_floor_planners<C, D, F, V, I>(
+    c: &mut Criterion,
+    name: &str,
+    ks: &[u32],
+    circuit: F,
+    v1: V,
+    instances: I,
+) where
+    C: Circuit<Fp> + Send + Sync,
+    D: Circuit<Fp> + Send + Sync,
+    F: Fn(u32) -> C,
+    V: Fn(C) -> D,
+    I: Fn(u32) -> Vec<Vec<Fp>>,
+{
+    bench_circuit_with_instances(
+        c,
+        &format!("{} SimpleFloorPlanner", name),
+        ks,
+        &circuit,
+        &instances,
+    );
+    bench_circuit_with_instances(
+        c,
+        &format!("{} V1", name),
+        ks,
+        |k| v1(circuit(k)),
+        &instances,
+    );
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable. The circuits are
+    // the same as those of their own benches
+    let ks = k_range(&[10, 14]);
+    let a_value = Value::known(Fp::from(2));
+
+    // IsZero lays every gate and every copy constraint in a region of its own
+    let repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroCircuit::<Fp>::repeats_for_k(k)))
+        .collect();
+    bench_floor_planners(
+        c,
+        "IsZero",
+        &ks,
+        |k| IsZeroCircuit::new(a_value, repeats[&k]),
+        IsZeroCircuit::with_floor_planner::<V1>,
+        |_| vec![],
+    );
+
+    let chip_repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroChipCircuit::<Fp>::repeats_for_k(k)))
+        .collect();
+    bench_floor_planners(
+        c,
+        "IsZero chip",
+        &ks,
+        |k| IsZeroChipCircuit::new(a_value, chip_repeats[&k]),
+        IsZeroChipCircuit::with_floor_planner::<V1>,
+        |_| vec![],
+    );
+
+    let public_repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroPublicCircuit::<Fp>::repeats_for_k(k, false)))
+        .collect();
+    bench_floor_planners(
+        c,
+        "IsZero public",
+        &ks,
+        |k| IsZeroPublicCircuit::new(a_value, public_repeats[&k], false),
+        IsZeroPublicCircuit::with_floor_planner::<V1>,
+        |k| IsZeroPublicCircuit::instances(Fp::from(2), public_repeats[&k], false),
+    );
+
+    let inputs = RangeCircuit::<Fp>::bench_inputs();
+    let range_repeats: HashMap<u32, u32> = ks
+        .iter()
+        .map(|&k| (k, RangeCircuit::<Fp>::bench_repeats_for_k(k)))
+        .collect();
+    // The main gate has a single instance column, which this circuit leaves empty
+    bench_floor_planners(
+        c,
+        "Range",
+        &ks,
+        |k| RangeCircuit::new(inputs.clone(), range_repeats[&k]),
+        RangeCircuit::with_floor_planner::<V1>,
+        |_| vec![vec![]],
+    );
+
+    // One signature verification takes between 2^17 and 2^18 rows, so smaller sizes set in
+    // `K_RANGE` are skipped. `tests/layout.rs` checks it (in an ignored test) and the Keccak
+    // circuits are satisfied under `V1`, which none of them is written for
+    let ecdsa_ks: Vec<u32> = k_range(&[18]).into_iter().filter(|&k| k >= 18).collect();
+    let signature = EcdsaVerifyCircuit::<Secp256k1, Fp>::random();
+    bench_floor_planners(
+        c,
+        "ECDSA",
+        &ecdsa_ks,
+        |_| signature.clone(),
+        EcdsaVerifyCircuit::with_floor_planner::<V1>,
+        |_| vec![vec![]],
+    );
+
+    // The Keccak circuits need at least 2^8 (bit) and 2^9 (packed) rows for these inputs
+    let keccak_inputs = vec![
+        vec![],
+        (0u8..1).collect::<Vec<_>>(),
+        (0u8..135).collect::<Vec<_>>(),
+        (0u8..136).collect::<Vec<_>>(),
+        (0u8..200).collect::<Vec<_>>(),
+    ];
+    let keccak_bit_ks: Vec<u32> = k_range(&[8]).into_iter().filter(|&k| k >= 8).collect();
+    bench_floor_planners(
+        c,
+        "Keccak bit",
+        &keccak_bit_ks,
+        |k| {
+            let mut circuit = KeccakBitCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&keccak_inputs);
+            circuit
+        },
+        WithFloorPlanner::<_, V1>::new,
+        |_| vec![],
+    );
+    let keccak_packed_ks: Vec<u32> = k_range(&[9]).into_iter().filter(|&k| k >= 9).collect();
+    bench_floor_planners(
+        c,
+        "Keccak packed",
+        &keccak_packed_ks,
+        |k| {
+            let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&keccak_inputs);
+            circuit
+        },
+        WithFloorPlanner::<_, V1>::new,
+        |_| vec![],
+    );
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,172 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{BenchmarkId, Criterion};
+
+use halo2::{
+    groth16::{
+        circuits::{
+            iszero, iszero_repeats_for_k, less_than_repeats_for_k, repeated_less_than,
+            LESS_THAN_BITS,
+        },
+        prepare_verifying_key, prove, setup, verify,
+    },
+    r1cs::{check_r1cs, R1cs},
+    results::{self, Phase},
+};
+use pairing::bn256::Fr;
+use rand_core::OsRng;
+use std::collections::BTreeMap;
+
+mod harness;
+use harness::{
+    benchmark_id, k_range, measure_memory, record_results, thread_pools, Measurements, Timing,
+    SAMPLE_SIZE,
+};
+
+/// Benches the Groth16 set-up, proving and verification of the R1CS built by `circuit` for every
+/// size in `ks`, under the criterion groups "`<name>` Groth16 `<phase>`", on every thread pool of
+/// the harness. `circuit(k)` should return a constraint system whose evaluation domain has `2^k`
+/// points, along with its witness.
+fn bench_groth16<F>(c: &mut Criterion, name: &str, ks: &[u32], circuit: F)
+where
+    F: Fn(u32) -> (R1cs<Fr>, Vec<Fr>),
+{
+    let circuits: BTreeMap<u32, (R1cs<Fr>, Vec<Fr>)> = ks
+        .iter()
+        .map(|&k| {
+            let (r1cs, witness) = circuit(k);
+            assert_eq!(check_r1cs(&r1cs, &witness), Ok(()));
+            (k, (r1cs, witness))
+        })
+        .collect();
+
+    let pools = thread_pools();
+    let mut timings: BTreeMap<usize, BTreeMap<(u32, Phase), Timing>> = BTreeMap::new();
+    let mut proof_sizes: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut memory = BTreeMap::new();
+    let mut vk_sizes: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut pk_sizes: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut instance_counts: BTreeMap<u32, usize> = BTreeMap::new();
+
+    // Prepare benching for the circuit specific set-up
+    let mut circuit_setup = c.benchmark_group(format!("{} Groth16 Circuit Set-up", name));
+    circuit_setup.sample_size(SAMPLE_SIZE);
+    for (&k, (r1cs, _)) in circuits.iter() {
+        memory.insert((k, Phase::Setup), measure_memory(|| setup(r1cs, OsRng)));
+        for (threads, pool) in pools.iter() {
+            let timing = timings
+                .entry(*threads)
+                .or_default()
+                .entry((k, Phase::Setup))
+                .or_default();
+            circuit_setup.bench_function(benchmark_id(None, *threads, k), |b| {
+                b.iter_custom(|iters| pool.install(|| timing.time(iters, || setup(r1cs, OsRng))));
+            });
+        }
+    }
+    circuit_setup.finish();
+
+    // Prepare benching for proof generation
+    let mut proof_generation = c.benchmark_group(format!("{} Groth16 Proof Generation", name));
+    proof_generation.sample_size(SAMPLE_SIZE);
+    for (&k, (r1cs, witness)) in circuits.iter() {
+        let pk = setup(r1cs, OsRng);
+        vk_sizes.insert(k, pk.vk.to_bytes().len());
+        pk_sizes.insert(k, pk.to_bytes().len());
+        memory.insert(
+            (k, Phase::Prove),
+            measure_memory(|| prove(&pk, r1cs, witness, OsRng)),
+        );
+        for (threads, pool) in pools.iter() {
+            let timing = timings
+                .entry(*threads)
+                .or_default()
+                .entry((k, Phase::Prove))
+                .or_default();
+            proof_generation.bench_function(benchmark_id(None, *threads, k), |b| {
+                b.iter_custom(|iters| {
+                    pool.install(|| timing.time(iters, || prove(&pk, r1cs, witness, OsRng)))
+                });
+            });
+        }
+    }
+    proof_generation.finish();
+
+    // Prepare benching for proof verification
+    let mut proof_verification = c.benchmark_group(format!("{} Groth16 Proof Verification", name));
+    proof_verification.sample_size(SAMPLE_SIZE);
+    for (&k, (r1cs, witness)) in circuits.iter() {
+        let pk = setup(r1cs, OsRng);
+        let pvk = prepare_verifying_key(&pk.vk);
+        let proof = prove(&pk, r1cs, witness, OsRng);
+        proof_sizes.insert(k, proof.to_bytes().len());
+        let public_inputs = r1cs.public_inputs(witness);
+        instance_counts.insert(k, public_inputs.len());
+        memory.insert(
+            (k, Phase::Verify),
+            measure_memory(|| {
+                verify(&pvk, public_inputs, &proof).expect("proof verification should not fail")
+            }),
+        );
+        for (threads, pool) in pools.iter() {
+            let timing = timings
+                .entry(*threads)
+                .or_default()
+                .entry((k, Phase::Verify))
+                .or_default();
+            proof_verification.bench_function(benchmark_id(None, *threads, k), |b| {
+                b.iter_custom(|iters| {
+                    pool.install(|| {
+                        timing.time(iters, || {
+                            verify(&pvk, public_inputs, &proof)
+                                .expect("proof verification should not fail")
+                        })
+                    })
+                });
+            });
+        }
+    }
+    proof_verification.finish();
+
+    for (threads, timings) in timings {
+        record_results(
+            results::GROTH16_RESULTS,
+            "groth16",
+            None,
+            Some(threads),
+            name,
+            &Measurements {
+                timings,
+                memory: memory.clone(),
+                proof_sizes: proof_sizes.clone(),
+                instance_counts: instance_counts.clone(),
+                vk_sizes: vk_sizes.clone(),
+                pk_sizes: pk_sizes.clone(),
+                srs_sizes: BTreeMap::new(),
+                rows_used: BTreeMap::new(),
+            },
+        );
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable
+    let ks = k_range(&[10, 14]);
+
+    // Same inputs as `groth16/iszero/input.json` and `groth16/range/input.json`
+    // `iszero.circom` exposes its outputs, as the Halo2 `IsZero public` circuit does
+    bench_groth16(c, "IsZero public", &ks, |k| {
+        iszero(Fr::from(2), iszero_repeats_for_k(k))
+    });
+    bench_groth16(c, "RepeatedLessThan", &ks, |k| {
+        repeated_less_than(
+            2,
+            3,
+            less_than_repeats_for_k(k, LESS_THAN_BITS),
+            LESS_THAN_BITS,
+        )
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,681 @@
+//! Shared criterion pipeline for the circuit benches.
+//!
+//! Every bench runs the same four phases (verifier key generation, prover key generation, proof
+//! generation and proof verification) and registers each of them as a criterion group called
+//! "`<name>` `<phase>`", with one benchmark per circuit size `k` so that a single run gives the
+//! scaling curve of every phase. Proof generation and verification are benched with GWC and a
+//! Blake2b transcript, or once per multi-open scheme set in `MULTIOPEN` and transcript set in
+//! `TRANSCRIPT` (`all` sweeping GWC and SHPLONK, and Blake2b, Keccak256 and Poseidon), under
+//! "`<variant>`/" where the variant is named by `halo2::prover::variant_name`. The mean time of
+//! every phase (and the proof size) is also appended to the Halo2 results file under that variant,
+//! see `halo2::results`.
+//!
+//! Every phase is benched on a rayon thread pool of all the threads of the machine, or on pools of
+//! each of the sizes set in `THREADS` (`all` sweeping 1, 2, 4, ... threads up to those of the
+//! machine), as "`<threads>` threads/`<k>`" (after the variant, if any), and recorded once per
+//! thread count, so that `cargo run --bin report` can print how each of them scales.
+//!
+//! The memory of every phase is measured by running it once more outside of criterion, on the
+//! global thread pool, with the counting allocator of `halo2::memory` installed as the global
+//! allocator of the bench binaries. Its peak and total allocations are recorded along with the
+//! times on every thread of the machine, with the size of the serialized SRS.
+//!
+//! The KZG parameters are random unless `PTAU` holds the path of a snarkjs `.ptau` file, in which
+//! case they are read from it so that Halo2 runs on the same powers of tau as the circom benches.
+//! Random parameters and the keys used outside of the key generation phases are cached on disk,
+//! see `halo2::cache`, and the proving key of every `k` is built once for proving and verifying.
+//!
+//! Not every bench uses every item of the harness.
+#![allow(dead_code)]
+
+use criterion::{black_box, BenchmarkId, Criterion};
+use halo2::{
+    cache::KeyCache,
+    layout,
+    memory::{self, CountingAllocator, Memory},
+    prover::{
+        instance_refs, prove_with_transcript, variant_name, verify_with_transcript, MultiOpen,
+    },
+    results::{self, Machine, Phase, PhaseResult},
+    transcript::TranscriptHash,
+};
+use halo2_proofs::{
+    dev::MockProver,
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{keygen_pk, keygen_vk, Circuit, ProvingKey},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::{
+    collections::BTreeMap,
+    env,
+    io::{self, Write},
+    iter,
+    time::{Duration, Instant},
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of samples taken for every phase; proving at large `k` takes seconds per iteration
+pub const SAMPLE_SIZE: usize = 10;
+
+/// Environment variable holding the comma separated circuit sizes to bench, e.g. `K_RANGE=10,14,18`
+const K_RANGE_VAR: &str = "K_RANGE";
+
+/// Returns the circuit sizes set in `K_RANGE`, or `default` when it isn't set
+pub fn k_range(default: &[u32]) -> Vec<u32> {
+    match env::var(K_RANGE_VAR) {
+        Ok(ks) => ks
+            .split(',')
+            .map(|k| {
+                k.trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{} should only contain integers", K_RANGE_VAR))
+            })
+            .collect(),
+        Err(_) => default.to_vec(),
+    }
+}
+
+/// Environment variable holding the comma separated multi-open schemes to bench, e.g.
+/// `MULTIOPEN=shplonk`, or `all`
+const MULTIOPEN_VAR: &str = "MULTIOPEN";
+
+/// Value of `MULTIOPEN`, `TRANSCRIPT` and `THREADS` sweeping every option
+const ALL: &str = "all";
+
+/// Returns the multi-open schemes set in `MULTIOPEN`, all of them when it is `all` and GWC when it
+/// isn't set
+pub fn multiopen_schemes() -> Vec<MultiOpen> {
+    match env::var(MULTIOPEN_VAR) {
+        Ok(schemes) if schemes.trim().eq_ignore_ascii_case(ALL) => MultiOpen::ALL.to_vec(),
+        Ok(schemes) => schemes
+            .split(',')
+            .map(|scheme| {
+                MultiOpen::parse(scheme.trim()).unwrap_or_else(|| {
+                    panic!("{} should only contain GWC or SHPLONK", MULTIOPEN_VAR)
+                })
+            })
+            .collect(),
+        Err(_) => vec![MultiOpen::Gwc],
+    }
+}
+
+/// Environment variable holding the comma separated transcripts to bench, e.g.
+/// `TRANSCRIPT=blake2b,keccak256`, or `all`
+const TRANSCRIPT_VAR: &str = "TRANSCRIPT";
+
+/// Returns the transcripts set in `TRANSCRIPT`, all of them when it is `all` and Blake2b when it
+/// isn't set
+pub fn transcripts() -> Vec<TranscriptHash> {
+    match env::var(TRANSCRIPT_VAR) {
+        Ok(transcripts) if transcripts.trim().eq_ignore_ascii_case(ALL) => {
+            TranscriptHash::ALL.to_vec()
+        }
+        Ok(transcripts) => transcripts
+            .split(',')
+            .map(|transcript| {
+                TranscriptHash::parse(transcript.trim()).unwrap_or_else(|| {
+                    panic!(
+                        "{} should only contain Blake2b, Keccak256 or Poseidon",
+                        TRANSCRIPT_VAR
+                    )
+                })
+            })
+            .collect(),
+        Err(_) => vec![TranscriptHash::Blake2b],
+    }
+}
+
+/// Environment variable holding the comma separated sizes of the thread pools to bench every phase
+/// on, e.g. `THREADS=1,16`, or `all`
+const THREADS_VAR: &str = "THREADS";
+
+/// Returns the thread counts set in `THREADS`, the powers of two below the number of threads of the
+/// machine followed by that number when it is `all`, and that number alone when it isn't set
+pub fn thread_counts() -> Vec<usize> {
+    let max = Machine::current().threads;
+    match env::var(THREADS_VAR) {
+        Ok(counts) if counts.trim().eq_ignore_ascii_case(ALL) => {
+            let mut counts: Vec<usize> = iter::successors(Some(1), |count| Some(count * 2))
+                .take_while(|&count| count < max)
+                .collect();
+            counts.push(max);
+            counts
+        }
+        Ok(counts) => counts
+            .split(',')
+            .map(|count| {
+                count
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&count| count > 0)
+                    .unwrap_or_else(|| {
+                        panic!("{} should only contain positive integers", THREADS_VAR)
+                    })
+            })
+            .collect(),
+        Err(_) => vec![max],
+    }
+}
+
+/// A rayon thread pool of every size returned by [`thread_counts`]. Work installed in a pool,
+/// including the parallel iterators of `halo2_proofs` and of the native Groth16 prover, only runs
+/// on its threads.
+pub fn thread_pools() -> Vec<(usize, ThreadPool)> {
+    thread_counts()
+        .into_iter()
+        .map(|threads| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap_or_else(|e| panic!("failed to build a pool of {} threads: {}", threads, e));
+            (threads, pool)
+        })
+        .collect()
+}
+
+/// Criterion id of the benchmark of size `k` on a pool of `threads` threads, for `variant` if the
+/// phase has several
+pub fn benchmark_id(variant: Option<&str>, threads: usize, k: u32) -> BenchmarkId {
+    match variant {
+        Some(variant) => BenchmarkId::new(format!("{}/{} threads", variant, threads), k),
+        None => BenchmarkId::new(format!("{} threads", threads), k),
+    }
+}
+
+/// Inputs of the Keccak benches, a few messages around the 136 byte rate of Keccak256 repeated
+/// `repeats` times
+pub fn keccak_inputs(repeats: usize) -> Vec<Vec<u8>> {
+    let inputs = [
+        vec![],
+        (0u8..1).collect::<Vec<_>>(),
+        (0u8..135).collect::<Vec<_>>(),
+        (0u8..136).collect::<Vec<_>>(),
+        (0u8..200).collect::<Vec<_>>(),
+    ];
+    inputs
+        .iter()
+        .cycle()
+        .take(inputs.len() * repeats)
+        .cloned()
+        .collect()
+}
+
+/// KZG parameters for circuits of `2^k` rows, read from the `.ptau` file set in `PTAU` if any and
+/// from the cache otherwise
+pub fn kzg_params(cache: &KeyCache, k: u32) -> ParamsKZG<Bn256> {
+    match cache.kzg_params(k) {
+        Ok(params) => params,
+        Err(e) => panic!("failed to read k = {} parameters: {}", k, e),
+    }
+}
+
+/// Writer counting the bytes written to it, and discarding them
+#[derive(Default)]
+pub struct ByteCounter {
+    pub bytes: usize,
+}
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Number of bytes `write` writes, e.g. `serialized_size(|writer| params.write(writer))`
+pub fn serialized_size(write: impl FnOnce(&mut ByteCounter) -> io::Result<()>) -> usize {
+    let mut counter = ByteCounter::default();
+    write(&mut counter).expect("writing to memory should not fail");
+    counter.bytes
+}
+
+/// Memory used by one run of `f`, whose output is only dropped once measured
+pub fn measure_memory<O>(f: impl FnOnce() -> O) -> Memory {
+    memory::measure(f).1
+}
+
+/// Total time spent in, and number of iterations of, one benchmark across all of the calls
+/// criterion made to it
+#[derive(Clone, Default)]
+pub struct Timing {
+    elapsed: Duration,
+    iters: u64,
+}
+
+impl Timing {
+    /// Runs `f` `iters` times, returning (and recording) the time it took
+    pub fn time<O>(&mut self, iters: u64, mut f: impl FnMut() -> O) -> Duration {
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(f());
+        }
+        self.record(start.elapsed(), iters)
+    }
+
+    pub fn record(&mut self, elapsed: Duration, iters: u64) -> Duration {
+        self.elapsed += elapsed;
+        self.iters += iters;
+        elapsed
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        self.elapsed.as_secs_f64() * 1000.0 / self.iters as f64
+    }
+}
+
+/// What was measured of the phases of one proving system (or one of its variants) for one circuit,
+/// keyed by circuit size
+#[derive(Clone, Default)]
+pub struct Measurements {
+    pub timings: BTreeMap<(u32, Phase), Timing>,
+    /// Memory used by one run of every phase, on every thread of the machine
+    pub memory: BTreeMap<(u32, Phase), Memory>,
+    pub proof_sizes: BTreeMap<u32, usize>,
+    /// Number of values of the instance columns of a proof
+    pub instance_counts: BTreeMap<u32, usize>,
+    /// Serialized size of the verifying key
+    pub vk_sizes: BTreeMap<u32, usize>,
+    /// Serialized size of the proving key, left empty for Halo2 whose proving keys can't be
+    /// serialized at this version of `halo2_proofs`
+    pub pk_sizes: BTreeMap<u32, usize>,
+    /// Serialized size of the parameters the circuit was set up with
+    pub srs_sizes: BTreeMap<u32, usize>,
+    /// Number of rows used by the circuit, as laid out by its floor planner
+    pub rows_used: BTreeMap<u32, usize>,
+}
+
+/// Benches key generation, proving and verification of the circuits built by `circuit` for
+/// every size in `ks`.
+///
+/// `circuit(k)` should build a circuit filling `2^k` rows. It is called once per proof so that
+/// circuits which consume their witness can be benched; building the circuit is not part of the
+/// measured time. `instances` holds the values of each instance column of the circuit.
+///
+/// Key generation ignores witness values, but some circuits (e.g. ECDSA and Keccak) don't lay out
+/// the same columns when built by `without_witnesses`, so keys are generated from full circuits.
+pub fn bench_circuit<C, F>(
+    c: &mut Criterion,
+    name: &str,
+    ks: &[u32],
+    circuit: F,
+    instances: Vec<Vec<Fr>>,
+) where
+    C: Circuit<Fr> + Send + Sync,
+    F: Fn(u32) -> C,
+{
+    bench_circuit_with_instances(c, name, ks, circuit, |_| instances.clone());
+}
+
+/// Same as [`bench_circuit`] for circuits whose instance values depend on their size: `instances(k)`
+/// holds the values of each instance column of `circuit(k)`.
+pub fn bench_circuit_with_instances<C, F, I>(
+    c: &mut Criterion,
+    name: &str,
+    ks: &[u32],
+    circuit: F,
+    instances: I,
+) where
+    C: Circuit<Fr> + Send + Sync,
+    F: Fn(u32) -> C,
+    I: Fn(u32) -> Vec<Vec<Fr>>,
+{
+    let instances: BTreeMap<u32, Vec<Vec<Fr>>> = ks.iter().map(|&k| (k, instances(k))).collect();
+
+    // Make sure the circuits are satisfied before spending time on benching them
+    for &k in ks {
+        let prover = match MockProver::run(k, &circuit(k), instances[&k].clone()) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    let cache = KeyCache::from_env();
+    let pools = thread_pools();
+    let transcripts = transcripts();
+    let variants: Vec<(MultiOpen, TranscriptHash)> = multiopen_schemes()
+        .into_iter()
+        .flat_map(|scheme| {
+            transcripts
+                .iter()
+                .map(move |&transcript| (scheme, transcript))
+        })
+        .collect();
+    // Key generation doesn't depend on the multi-open scheme nor on the transcript, proving and
+    // verification do. Both are timed for every thread count
+    let mut timings: BTreeMap<usize, BTreeMap<(u32, Phase), Timing>> = BTreeMap::new();
+    let mut variant_timings: BTreeMap<
+        (usize, MultiOpen, TranscriptHash),
+        BTreeMap<(u32, Phase), Timing>,
+    > = BTreeMap::new();
+    let mut proof_sizes: BTreeMap<(MultiOpen, TranscriptHash), BTreeMap<u32, usize>> =
+        BTreeMap::new();
+    // Memory is measured once, on the global thread pool
+    let mut memory: BTreeMap<(u32, Phase), Memory> = BTreeMap::new();
+    let mut variant_memory: BTreeMap<(MultiOpen, TranscriptHash), BTreeMap<(u32, Phase), Memory>> =
+        BTreeMap::new();
+    let mut srs_sizes: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut vk_sizes: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut rows_used: BTreeMap<u32, usize> = BTreeMap::new();
+    let instance_counts: BTreeMap<u32, usize> = instances
+        .iter()
+        .map(|(&k, columns)| (k, columns.iter().map(Vec::len).sum()))
+        .collect();
+
+    // Prepare benching for verifier key generation
+    let mut verifier_key_generation =
+        c.benchmark_group(format!("{} Verifier Key Generation", name));
+    verifier_key_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let keygen_circuit = circuit(k);
+        srs_sizes.insert(k, serialized_size(|writer| params.write(writer)));
+        rows_used.insert(k, layout::rows_used(&keygen_circuit));
+        memory.insert(
+            (k, Phase::KeygenVk),
+            measure_memory(|| {
+                keygen_vk(&params, &keygen_circuit).expect("keygen_vk should not fail")
+            }),
+        );
+        for (threads, pool) in pools.iter() {
+            let timing = timings
+                .entry(*threads)
+                .or_default()
+                .entry((k, Phase::KeygenVk))
+                .or_default();
+            let id = benchmark_id(None, *threads, k);
+            verifier_key_generation.bench_function(id, |b| {
+                b.iter_custom(|iters| {
+                    pool.install(|| {
+                        timing.time(iters, || {
+                            keygen_vk(&params, &keygen_circuit).expect("keygen_vk should not fail")
+                        })
+                    })
+                });
+            });
+        }
+    }
+    verifier_key_generation.finish();
+
+    // Prepare benching for prover key generation
+    let mut prover_key_generation = c.benchmark_group(format!("{} Prover Key Generation", name));
+    prover_key_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let keygen_circuit = circuit(k);
+        let vk = cache
+            .vk(name, &params, &keygen_circuit)
+            .expect("keygen_vk should not fail");
+        vk_sizes.insert(k, serialized_size(|writer| vk.write(writer)));
+        memory.insert(
+            (k, Phase::KeygenPk),
+            measure_memory(|| {
+                keygen_pk(&params, vk.clone(), &keygen_circuit).expect("keygen_pk should not fail")
+            }),
+        );
+        for (threads, pool) in pools.iter() {
+            let timing = timings
+                .entry(*threads)
+                .or_default()
+                .entry((k, Phase::KeygenPk))
+                .or_default();
+            let id = benchmark_id(None, *threads, k);
+            prover_key_generation.bench_function(id, |b| {
+                b.iter_custom(|iters| {
+                    pool.install(|| {
+                        timing.time(iters, || {
+                            keygen_pk(&params, vk.clone(), &keygen_circuit)
+                                .expect("keygen_pk should not fail")
+                        })
+                    })
+                });
+            });
+        }
+    }
+    prover_key_generation.finish();
+
+    // The proving key of every size, shared by proof generation and verification
+    let pks: BTreeMap<u32, ProvingKey<G1Affine>> = ks
+        .iter()
+        .map(|&k| {
+            let pk = cache
+                .pk(name, &kzg_params(&cache, k), &circuit(k))
+                .expect("keygen should not fail");
+            (k, pk)
+        })
+        .collect();
+
+    // Prepare benching for proof generation
+    let mut proof_generation = c.benchmark_group(format!("{} Proof Generation", name));
+    proof_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let pk = &pks[&k];
+        let instance_refs = instance_refs(&instances[&k]);
+        let instances: &[&[&[Fr]]] = &[instance_refs.as_slice()];
+        for &(scheme, transcript) in variants.iter() {
+            let variant = variant_name(scheme, transcript);
+            // Building the circuit is not part of proving
+            let circuits = [circuit(k)];
+            let proof_memory = measure_memory(|| {
+                prove_with_transcript(scheme, transcript, &params, pk, &circuits, instances)
+                    .expect("proof generation should not fail")
+            });
+            variant_memory
+                .entry((scheme, transcript))
+                .or_default()
+                .insert((k, Phase::Prove), proof_memory);
+            for (threads, pool) in pools.iter() {
+                let timing = variant_timings
+                    .entry((*threads, scheme, transcript))
+                    .or_default()
+                    .entry((k, Phase::Prove))
+                    .or_default();
+                let id = benchmark_id(Some(&variant), *threads, k);
+                proof_generation.bench_function(id, |b| {
+                    b.iter_custom(|iters| {
+                        // Building the circuits is not part of proving
+                        let circuits: Vec<C> = (0..iters).map(|_| circuit(k)).collect();
+                        pool.install(|| {
+                            let start = Instant::now();
+                            for circuit in circuits {
+                                black_box(
+                                    prove_with_transcript(
+                                        scheme,
+                                        transcript,
+                                        &params,
+                                        pk,
+                                        &[circuit],
+                                        instances,
+                                    )
+                                    .expect("proof generation should not fail"),
+                                );
+                            }
+                            timing.record(start.elapsed(), iters)
+                        })
+                    });
+                });
+            }
+        }
+    }
+    proof_generation.finish();
+
+    // Prepare benching for proof verification
+    let mut proof_verification = c.benchmark_group(format!("{} Proof Verification", name));
+    proof_verification.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let pk = &pks[&k];
+        let instance_refs = instance_refs(&instances[&k]);
+        let instances: &[&[&[Fr]]] = &[instance_refs.as_slice()];
+        for &(scheme, transcript) in variants.iter() {
+            let variant = variant_name(scheme, transcript);
+            let proof =
+                prove_with_transcript(scheme, transcript, &params, pk, &[circuit(k)], instances)
+                    .expect("proof generation should not fail");
+            proof_sizes
+                .entry((scheme, transcript))
+                .or_default()
+                .insert(k, proof.len());
+            let verification_memory = measure_memory(|| {
+                verify_with_transcript(scheme, transcript, &params, pk.get_vk(), instances, &proof)
+                    .expect("proof verification should not fail")
+            });
+            variant_memory
+                .entry((scheme, transcript))
+                .or_default()
+                .insert((k, Phase::Verify), verification_memory);
+            for (threads, pool) in pools.iter() {
+                let timing = variant_timings
+                    .entry((*threads, scheme, transcript))
+                    .or_default()
+                    .entry((k, Phase::Verify))
+                    .or_default();
+                let id = benchmark_id(Some(&variant), *threads, k);
+                proof_verification.bench_function(id, |b| {
+                    b.iter_custom(|iters| {
+                        pool.install(|| {
+                            timing.time(iters, || {
+                                verify_with_transcript(
+                                    scheme,
+                                    transcript,
+                                    &params,
+                                    pk.get_vk(),
+                                    instances,
+                                    &proof,
+                                )
+                                .expect("proof verification should not fail")
+                            })
+                        })
+                    });
+                });
+            }
+        }
+    }
+    proof_verification.finish();
+
+    // Every scheme and transcript is recorded as a variant, along with the key generation times
+    // they share, once per thread count
+    for &(threads, _) in pools.iter() {
+        for &(scheme, transcript) in variants.iter() {
+            let mut measurements = Measurements {
+                timings: timings.get(&threads).cloned().unwrap_or_default(),
+                memory: memory.clone(),
+                proof_sizes: proof_sizes
+                    .get(&(scheme, transcript))
+                    .cloned()
+                    .unwrap_or_default(),
+                instance_counts: instance_counts.clone(),
+                vk_sizes: vk_sizes.clone(),
+                pk_sizes: BTreeMap::new(),
+                srs_sizes: srs_sizes.clone(),
+                rows_used: rows_used.clone(),
+            };
+            measurements.timings.extend(
+                variant_timings
+                    .remove(&(threads, scheme, transcript))
+                    .unwrap_or_default(),
+            );
+            measurements.memory.extend(
+                variant_memory
+                    .get(&(scheme, transcript))
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            record_results(
+                results::HALO2_RESULTS,
+                "halo2",
+                Some(&variant_name(scheme, transcript)),
+                Some(threads),
+                name,
+                &measurements,
+            );
+        }
+    }
+}
+
+/// Appends the mean time of every phase of `system` (or of one of its variants) to `file` in the
+/// results directory, with the other measurements of the phase. `threads` is the size of the
+/// thread pool the phases ran on, if they didn't run on the global one; memory is only recorded
+/// with the results of the pool of every thread of the machine, which is what it was measured on.
+///
+/// The sizes of the verifying and proving keys and of the SRS are recorded along with the phase
+/// generating them on every thread count. The number of rows used by the circuit is
+/// recorded along with verifier key generation, which lays it out, and the size of the proof and
+/// its number of public inputs along with proof generation.
+pub fn record_results(
+    file: &str,
+    system: &str,
+    variant: Option<&str>,
+    threads: Option<usize>,
+    name: &str,
+    measurements: &Measurements,
+) {
+    let machine = Machine::current();
+    let on_all_threads = threads.map_or(true, |threads| threads == machine.threads);
+    let records: Vec<PhaseResult> = measurements
+        .timings
+        .iter()
+        .filter(|(_, timing)| timing.iters > 0)
+        .map(|(&(k, phase), timing)| {
+            let memory = measurements
+                .memory
+                .get(&(k, phase))
+                .copied()
+                .filter(|_| on_all_threads);
+            let generates_vk = matches!(phase, Phase::KeygenVk | Phase::Setup);
+            let generates_pk = matches!(phase, Phase::KeygenPk | Phase::Setup);
+            PhaseResult {
+                system: system.to_string(),
+                circuit: name.to_string(),
+                k,
+                phase,
+                variant: variant.map(str::to_string),
+                mean_ms: timing.mean_ms(),
+                proof_bytes: match phase {
+                    Phase::Prove => measurements.proof_sizes.get(&k).copied(),
+                    _ => None,
+                },
+                instance_values: match phase {
+                    Phase::Prove => measurements.instance_counts.get(&k).copied(),
+                    _ => None,
+                },
+                vk_bytes: measurements
+                    .vk_sizes
+                    .get(&k)
+                    .copied()
+                    .filter(|_| generates_vk),
+                pk_bytes: measurements
+                    .pk_sizes
+                    .get(&k)
+                    .copied()
+                    .filter(|_| generates_pk),
+                srs_bytes: measurements
+                    .srs_sizes
+                    .get(&k)
+                    .copied()
+                    .filter(|_| generates_pk),
+                rows_used: match phase {
+                    Phase::KeygenVk => measurements.rows_used.get(&k).copied(),
+                    _ => None,
+                },
+                memory,
+                threads,
+                machine: machine.clone(),
+            }
+        })
+        .collect();
+
+    let path = results::results_dir().join(file);
+    if let Err(e) = results::append(&path, &records) {
+        eprintln!("failed to write results to {}: {}", path.display(), e);
+    }
+}
@@ -0,0 +1,237 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{black_box, BenchmarkId, Criterion};
+
+use halo2::{
+    ecdsa::EcdsaVerifyCircuit,
+    ipa,
+    iszero::IsZeroCircuit,
+    prover::instance_refs,
+    range::RangeCircuit,
+    results::{self, Phase},
+};
+use halo2_proofs::{
+    circuit::Value,
+    dev::MockProver,
+    halo2curves::pasta::Fp,
+    plonk::{keygen_pk, keygen_vk, Circuit},
+    poly::commitment::Params,
+};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use std::{collections::HashMap, time::Instant};
+
+mod harness;
+use harness::{
+    k_range, measure_memory, record_results, serialized_size, Measurements, SAMPLE_SIZE,
+};
+
+/// Benches the IPA parameter generation, key generation, proving and verification of the circuits
+/// built by `circuit` for every size in `ks`, under the criterion groups "`<name>` IPA `<phase>`".
+///
+/// Same pipeline as the KZG one of the harness, plus the generation of the parameters: IPA needs
+/// no trusted setup, so generating them is part of what a prover or verifier pays for.
+fn bench_ipa<C, F>(c: &mut Criterion, name: &str, ks: &[u32], circuit: F, instances: Vec<Vec<Fp>>)
+where
+    C: Circuit<Fp>,
+    F: Fn(u32) -> C,
+{
+    // Make sure the circuits are satisfied before spending time on benching them
+    for &k in ks {
+        let prover = match MockProver::run(k, &circuit(k), instances.clone()) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    let instance_refs = instance_refs(&instances);
+    let instances: &[&[&[Fp]]] = &[instance_refs.as_slice()];
+    let mut measurements = Measurements::default();
+    let instance_count: usize = instance_refs.iter().map(|column| column.len()).sum();
+
+    // Prepare benching for parameter generation
+    let mut parameter_generation = c.benchmark_group(format!("{} IPA Parameter Generation", name));
+    parameter_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = ipa::params(k);
+        measurements
+            .srs_sizes
+            .insert(k, serialized_size(|writer| params.write(writer)));
+        measurements
+            .memory
+            .insert((k, Phase::Params), measure_memory(|| ipa::params(k)));
+        let timing = measurements.timings.entry((k, Phase::Params)).or_default();
+        parameter_generation.bench_function(BenchmarkId::from_parameter(k), |b| {
+            b.iter_custom(|iters| timing.time(iters, || ipa::params(k)));
+        });
+    }
+    parameter_generation.finish();
+
+    // Prepare benching for verifier key generation
+    let mut verifier_key_generation =
+        c.benchmark_group(format!("{} IPA Verifier Key Generation", name));
+    verifier_key_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = ipa::params(k);
+        let keygen_circuit = circuit(k);
+        measurements.memory.insert(
+            (k, Phase::KeygenVk),
+            measure_memory(|| {
+                keygen_vk(&params, &keygen_circuit).expect("keygen_vk should not fail")
+            }),
+        );
+        let timing = measurements
+            .timings
+            .entry((k, Phase::KeygenVk))
+            .or_default();
+        verifier_key_generation.bench_function(BenchmarkId::from_parameter(k), |b| {
+            b.iter_custom(|iters| {
+                timing.time(iters, || {
+                    keygen_vk(&params, &keygen_circuit).expect("keygen_vk should not fail")
+                })
+            });
+        });
+    }
+    verifier_key_generation.finish();
+
+    // Prepare benching for prover key generation
+    let mut prover_key_generation =
+        c.benchmark_group(format!("{} IPA Prover Key Generation", name));
+    prover_key_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = ipa::params(k);
+        let keygen_circuit = circuit(k);
+        let vk = keygen_vk(&params, &keygen_circuit).expect("keygen_vk should not fail");
+        measurements
+            .vk_sizes
+            .insert(k, serialized_size(|writer| vk.write(writer)));
+        measurements.memory.insert(
+            (k, Phase::KeygenPk),
+            measure_memory(|| {
+                keygen_pk(&params, vk.clone(), &keygen_circuit).expect("keygen_pk should not fail")
+            }),
+        );
+        let timing = measurements
+            .timings
+            .entry((k, Phase::KeygenPk))
+            .or_default();
+        prover_key_generation.bench_function(BenchmarkId::from_parameter(k), |b| {
+            b.iter_custom(|iters| {
+                timing.time(iters, || {
+                    keygen_pk(&params, vk.clone(), &keygen_circuit)
+                        .expect("keygen_pk should not fail")
+                })
+            });
+        });
+    }
+    prover_key_generation.finish();
+
+    // Prepare benching for proof generation
+    let mut proof_generation = c.benchmark_group(format!("{} IPA Proof Generation", name));
+    proof_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = ipa::params(k);
+        let pk = ipa::keygen(&params, &circuit(k)).expect("keygen should not fail");
+        measurements.memory.insert(
+            (k, Phase::Prove),
+            measure_memory(|| {
+                ipa::prove(&params, &pk, &[circuit(k)], instances)
+                    .expect("proof generation should not fail")
+            }),
+        );
+        let timing = measurements.timings.entry((k, Phase::Prove)).or_default();
+        proof_generation.bench_function(BenchmarkId::from_parameter(k), |b| {
+            b.iter_custom(|iters| {
+                // Building the circuits is not part of proving
+                let circuits: Vec<C> = (0..iters).map(|_| circuit(k)).collect();
+                let start = Instant::now();
+                for circuit in circuits {
+                    black_box(
+                        ipa::prove(&params, &pk, &[circuit], instances)
+                            .expect("proof generation should not fail"),
+                    );
+                }
+                timing.record(start.elapsed(), iters)
+            });
+        });
+    }
+    proof_generation.finish();
+
+    // Prepare benching for proof verification
+    let mut proof_verification = c.benchmark_group(format!("{} IPA Proof Verification", name));
+    proof_verification.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = ipa::params(k);
+        let pk = ipa::keygen(&params, &circuit(k)).expect("keygen should not fail");
+        let proof = ipa::prove(&params, &pk, &[circuit(k)], instances)
+            .expect("proof generation should not fail");
+        measurements.proof_sizes.insert(k, proof.len());
+        measurements.instance_counts.insert(k, instance_count);
+        measurements.memory.insert(
+            (k, Phase::Verify),
+            measure_memory(|| {
+                ipa::verify(&params, pk.get_vk(), instances, &proof)
+                    .expect("proof verification should not fail")
+            }),
+        );
+        let timing = measurements.timings.entry((k, Phase::Verify)).or_default();
+        proof_verification.bench_function(BenchmarkId::from_parameter(k), |b| {
+            b.iter_custom(|iters| {
+                timing.time(iters, || {
+                    ipa::verify(&params, pk.get_vk(), instances, &proof)
+                        .expect("proof verification should not fail")
+                })
+            });
+        });
+    }
+    proof_verification.finish();
+
+    record_results(
+        results::HALO2_RESULTS,
+        "halo2",
+        Some("IPA"),
+        None,
+        name,
+        &measurements,
+    );
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable
+    let ks = k_range(&[10, 14]);
+
+    // Same circuits and inputs as the KZG benches, over the scalar field of Vesta
+    let repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroCircuit::<Fp>::repeats_for_k(k)))
+        .collect();
+    bench_ipa(
+        c,
+        "IsZero",
+        &ks,
+        |k| IsZeroCircuit::new(Value::known(Fp::from(2)), repeats[&k]),
+        vec![],
+    );
+
+    let inputs = RangeCircuit::<Fp>::bench_inputs();
+    let range_repeats: HashMap<u32, u32> = ks
+        .iter()
+        .map(|&k| (k, RangeCircuit::<Fp>::bench_repeats_for_k(k)))
+        .collect();
+    bench_ipa(
+        c,
+        "Range",
+        &ks,
+        |k| RangeCircuit::new(inputs.clone(), range_repeats[&k]),
+        vec![vec![]],
+    );
+
+    // One signature verification takes between 2^17 and 2^18 rows, see `benches/ecdsa.rs`, so
+    // smaller sizes set in `K_RANGE` are skipped
+    let ecdsa_ks: Vec<u32> = k_range(&[18]).into_iter().filter(|&k| k >= 18).collect();
+    let circuit = EcdsaVerifyCircuit::<Secp256k1, Fp>::random();
+    bench_ipa(c, "ECDSA", &ecdsa_ks, |_| circuit.clone(), vec![vec![]]);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
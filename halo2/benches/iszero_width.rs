@@ -0,0 +1,55 @@
+#[macro_use]
+extern crate criterion;
+use criterion::Criterion;
+
+use halo2::{
+    fit::fits,
+    iszero::{IsZeroCircuit, IsZeroWideCircuit},
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr as Fp};
+
+mod harness;
+use harness::{bench_circuit, k_range};
+
+/// Benches the IsZero workload of every size in `ks` on a `StandardPlonk` of `WIDTH` lanes.
+///
+/// The workload of size `k` is the number of repeats `IsZeroCircuit` fits in `2^k` rows, as many
+/// checks as the circom circuit of `2^k` constraints. A wider circuit lays them on fewer rows, so
+/// it is benched at the smallest size it fits in, as "IsZero 2^`<k>` width `<WIDTH>`".
+fn bench_width<const WIDTH: usize>(c: &mut Criterion, ks: &[u32]) {
+    let a_value = Value::known(Fp::from(2));
+    for &k in ks {
+        let repeats = IsZeroCircuit::<Fp>::repeats_for_k(k);
+        let build = |repeats| {
+            (
+                IsZeroWideCircuit::<Fp, WIDTH>::new(a_value, repeats),
+                vec![],
+            )
+        };
+        let wide_k = (1..=k)
+            .find(|&wide_k| fits(wide_k, repeats, &build))
+            .expect("a wider circuit should not need more rows");
+        bench_circuit(
+            c,
+            &format!("IsZero 2^{} width {}", k, WIDTH),
+            &[wide_k],
+            |_| IsZeroWideCircuit::<Fp, WIDTH>::new(a_value, repeats),
+            vec![],
+        );
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Workload sizes to bench, overridden by the `K_RANGE` environment variable
+    let ks = k_range(&[10, 14]);
+
+    // The width is part of the constraint system, so every width is a circuit of its own. Width 1
+    // is the layout of `IsZeroCircuit`
+    bench_width::<1>(c, &ks);
+    bench_width::<2>(c, &ks);
+    bench_width::<4>(c, &ks);
+    bench_width::<8>(c, &ks);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -1,146 +1,35 @@
 #[macro_use]
 extern crate criterion;
-use criterion::{BenchmarkId, Criterion};
-
-use halo2wrong::halo2::{
-    halo2curves::bn256::{Bn256, G1Affine},
-    plonk::*,
-    poly::{
-        commitment::ParamsProver,
-        kzg::{
-            commitment::{KZGCommitmentScheme, ParamsKZG},
-            multiopen::{ProverGWC, VerifierGWC},
-            strategy::SingleStrategy,
-        },
-    },
-    transcript::{
-        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
-    },
-};
-use rand_core::OsRng;
+use criterion::Criterion;
 
+use halo2::fit::max_repeats;
+use halo2_proofs::halo2curves::bn256::Fr;
+use std::collections::HashMap;
 use zkevm_circuits::keccak_circuit::keccak_packed::KeccakPackedCircuit;
 
-fn criterion_benchmark(c: &mut Criterion) {
-    let k = 9;
-    let inputs = vec![
-        vec![],
-        (0u8..1).collect::<Vec<_>>(),
-        (0u8..135).collect::<Vec<_>>(),
-        (0u8..136).collect::<Vec<_>>(),
-        (0u8..200).collect::<Vec<_>>(),
-    ];
+mod harness;
+use harness::{bench_circuit, k_range, keccak_inputs};
 
+/// Circuit of `2^k` rows hashing `repeats` times the inputs of [`keccak_inputs`]
+fn circuit(k: u32, repeats: usize) -> KeccakPackedCircuit<Fr> {
     let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
-    circuit.generate_witness(&inputs);
-
-    // Prepare benching for verifier key generation
-    let mut verifier_key_generation = c.benchmark_group("Keccak packed Verifier Key Generation");
-    verifier_key_generation.sample_size(10);
-    {
-        let params: ParamsKZG<Bn256> = ParamsKZG::<Bn256>::new(k);
-
-        verifier_key_generation.bench_with_input(
-            BenchmarkId::from_parameter(k),
-            &(&params, &circuit),
-            |b, &(params, circuit)| {
-                b.iter(|| {
-                    keygen_vk(params, circuit).expect("keygen_vk should not fail");
-                });
-            },
-        );
-    }
-    verifier_key_generation.finish();
-
-    // Prepare benching for prover key generation
-    let mut prover_key_generation = c.benchmark_group("Keccak packed Prover Key Generation");
-    prover_key_generation.sample_size(10);
-    {
-        let params: ParamsKZG<Bn256> = ParamsKZG::<Bn256>::new(k);
-        let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
-
-        prover_key_generation.bench_with_input(
-            BenchmarkId::from_parameter(k),
-            &(&params, &circuit, &vk),
-            |b, &(params, circuit, vk)| {
-                b.iter(|| {
-                    keygen_pk(params, vk.clone(), circuit).expect("keygen_pk should not fail");
-                });
-            },
-        );
-    }
-    prover_key_generation.finish();
-
-    // Prepare benching for proof generation
-    let mut proof_generation = c.benchmark_group("Keccak packed Proof Generation");
-    proof_generation.sample_size(10);
-    {
-        let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
-        circuit.generate_witness(&inputs);
-        let params: ParamsKZG<Bn256> = ParamsKZG::<Bn256>::new(k);
-        let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
-        let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
-        let mut transcript: Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>> =
-            Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
-
-        proof_generation.bench_with_input(
-            BenchmarkId::from_parameter(k),
-            &(&params, &pk),
-            |b, &(params, pk)| {
-                b.iter(|| {
-                    let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
-                    circuit.generate_witness(&inputs);
-                    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
-                        &params,
-                        &pk,
-                        &[circuit],
-                        &[&[]],
-                        OsRng,
-                        &mut transcript,
-                    )
-                    .expect("proof generation should not fail")
-                });
-            },
-        );
-    }
-    proof_generation.finish();
-
-    // Prepare benching for proof verification
-    let mut proof_verification = c.benchmark_group("Keccak packed Proof Verification");
-    proof_verification.sample_size(10);
-    {
-        let params: ParamsKZG<Bn256> = ParamsKZG::new(k);
-        let strategy = SingleStrategy::new(&params);
-        let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
-        let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
-        let mut transcript: Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>> =
-            Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
-        create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
-            &params,
-            &pk,
-            &[circuit],
-            &[&[]],
-            OsRng,
-            &mut transcript,
-        )
-        .expect("proof generation should not fail");
-        let proof = transcript.finalize();
-        let transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]);
+    circuit.generate_witness(&keccak_inputs(repeats));
+    circuit
+}
 
-        proof_verification.bench_with_input(BenchmarkId::from_parameter(k), &(), |b, ()| {
-            b.iter(|| {
-                verify_proof::<_, VerifierGWC<Bn256>, _, _, _>(
-                    &params,
-                    pk.get_vk(),
-                    strategy.clone(),
-                    &[&[]],
-                    &mut transcript.clone(),
-                )
-                .unwrap();
-            });
-        });
-    }
-    proof_verification.finish();
+fn criterion_benchmark(c: &mut Criterion) {
+    // The circuit is sized to 2^k rows, one set of inputs needs at least 2^9 of them so smaller
+    // sizes set in the `K_RANGE` environment variable are skipped
+    let ks: Vec<u32> = k_range(&[9, 12]).into_iter().filter(|&k| k >= 9).collect();
+
+    // The inputs are hashed as many times as fit in the 2^k rows. Fit them to each size once,
+    // rather than every time a circuit is built
+    let repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, max_repeats(k, |repeats| (circuit(k, repeats), vec![]))))
+        .collect();
+
+    bench_circuit(c, "Keccak packed", &ks, |k| circuit(k, repeats[&k]), vec![]);
 }
 
 criterion_group!(benches, criterion_benchmark);
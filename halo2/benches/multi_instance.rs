@@ -0,0 +1,278 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+
+use halo2::{
+    cache::KeyCache,
+    ecdsa::EcdsaVerifyCircuit,
+    iszero::IsZeroCircuit,
+    prover::{prove_many, verify_many, MultiOpen},
+    range::RangeCircuit,
+    results::{self, Phase},
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr, plonk::Circuit};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    time::Instant,
+};
+use zkevm_circuits::keccak_circuit::{
+    keccak_bit::KeccakBitCircuit, keccak_packed::KeccakPackedCircuit,
+};
+
+mod harness;
+use harness::{
+    k_range, kzg_params, measure_memory, multiopen_schemes, record_results, Measurements,
+    SAMPLE_SIZE,
+};
+
+/// Environment variable holding the comma separated numbers of circuit instances to prove
+/// together, e.g. `INSTANCES=1,16`
+const INSTANCES_VAR: &str = "INSTANCES";
+
+/// Returns the numbers of instances set in `INSTANCES`, or 1, 2, 4 and 8 when it isn't set
+fn instance_counts() -> Vec<usize> {
+    match env::var(INSTANCES_VAR) {
+        Ok(counts) => counts
+            .split(',')
+            .map(|count| {
+                count
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{} should only contain integers", INSTANCES_VAR))
+            })
+            .collect(),
+        Err(_) => vec![1, 2, 4, 8],
+    }
+}
+
+/// Name proofs of `count` instances are benched and recorded under
+fn instances_variant(scheme: MultiOpen, count: usize) -> String {
+    format!("{} {}x", scheme.name(), count)
+}
+
+/// Benches proving and verifying `count` instances of the circuit in a single proof, for every
+/// count in `INSTANCES` and size in `ks`, under the criterion groups "`<name>` Multi-instance
+/// Proof Generation" and "`<name>` Multi-instance Proof Verification".
+///
+/// `circuit(k, i)` builds the `i`th instance of the circuit filling `2^k` rows, so that every
+/// instance can have its own witness, and `public_inputs(k, i)` the values of its instance
+/// columns.
+/// Criterion reports the time of the whole proof, the results the time per instance (with the size
+/// of the whole proof), to be compared with proving every instance on its own.
+fn bench_multi_instance<C, F, I>(
+    c: &mut Criterion,
+    name: &str,
+    ks: &[u32],
+    circuit: F,
+    public_inputs: I,
+) where
+    C: Circuit<Fr>,
+    F: Fn(u32, usize) -> C,
+    I: Fn(u32, usize) -> Vec<Vec<Fr>>,
+{
+    let cache = KeyCache::from_env();
+    let schemes = multiopen_schemes();
+    let counts = instance_counts();
+    let mut measurements: BTreeMap<(MultiOpen, usize), Measurements> = BTreeMap::new();
+
+    // Prepare benching for proof generation
+    let mut proof_generation =
+        c.benchmark_group(format!("{} Multi-instance Proof Generation", name));
+    proof_generation.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let pk = cache
+            .pk(name, &params, &circuit(k, 0))
+            .expect("keygen should not fail");
+        for &scheme in schemes.iter() {
+            for &count in counts.iter() {
+                let instances: Vec<Vec<Vec<Fr>>> =
+                    (0..count).map(|i| public_inputs(k, i)).collect();
+                let measurements = measurements.entry((scheme, count)).or_default();
+                measurements.memory.insert(
+                    (k, Phase::Prove),
+                    measure_memory(|| {
+                        let circuits: Vec<C> = (0..count).map(|i| circuit(k, i)).collect();
+                        prove_many(scheme, &params, &pk, &circuits, &instances)
+                            .expect("proof generation should not fail")
+                    }),
+                );
+                let timing = measurements.timings.entry((k, Phase::Prove)).or_default();
+                proof_generation.throughput(Throughput::Elements(count as u64));
+                let id = BenchmarkId::new(instances_variant(scheme, count), k);
+                proof_generation.bench_function(id, |b| {
+                    b.iter_custom(|iters| {
+                        // Building the circuits is not part of proving
+                        let proofs: Vec<Vec<C>> = (0..iters)
+                            .map(|_| (0..count).map(|i| circuit(k, i)).collect())
+                            .collect();
+                        let start = Instant::now();
+                        for circuits in proofs {
+                            black_box(
+                                prove_many(scheme, &params, &pk, &circuits, &instances)
+                                    .expect("proof generation should not fail"),
+                            );
+                        }
+                        let elapsed = start.elapsed();
+                        // Every iteration proves `count` instances
+                        timing.record(elapsed, iters * count as u64);
+                        elapsed
+                    });
+                });
+            }
+        }
+    }
+    proof_generation.finish();
+
+    // Prepare benching for proof verification
+    let mut proof_verification =
+        c.benchmark_group(format!("{} Multi-instance Proof Verification", name));
+    proof_verification.sample_size(SAMPLE_SIZE);
+    for &k in ks {
+        let params = kzg_params(&cache, k);
+        let pk = cache
+            .pk(name, &params, &circuit(k, 0))
+            .expect("keygen should not fail");
+        for &scheme in schemes.iter() {
+            for &count in counts.iter() {
+                let instances: Vec<Vec<Vec<Fr>>> =
+                    (0..count).map(|i| public_inputs(k, i)).collect();
+                let circuits: Vec<C> = (0..count).map(|i| circuit(k, i)).collect();
+                let proof = prove_many(scheme, &params, &pk, &circuits, &instances)
+                    .expect("proof generation should not fail");
+                let measurements = measurements.entry((scheme, count)).or_default();
+                measurements.proof_sizes.insert(k, proof.len());
+                // Public inputs of all of the instances the proof is for
+                measurements
+                    .instance_counts
+                    .insert(k, instances.iter().flatten().map(Vec::len).sum());
+                measurements.memory.insert(
+                    (k, Phase::Verify),
+                    measure_memory(|| {
+                        verify_many(scheme, &params, pk.get_vk(), &instances, &proof)
+                            .expect("proof verification should not fail")
+                    }),
+                );
+                let timing = measurements.timings.entry((k, Phase::Verify)).or_default();
+                proof_verification.throughput(Throughput::Elements(count as u64));
+                let id = BenchmarkId::new(instances_variant(scheme, count), k);
+                proof_verification.bench_function(id, |b| {
+                    b.iter_custom(|iters| {
+                        let start = Instant::now();
+                        for _ in 0..iters {
+                            verify_many(scheme, &params, pk.get_vk(), &instances, &proof)
+                                .expect("proof verification should not fail");
+                        }
+                        let elapsed = start.elapsed();
+                        timing.record(elapsed, iters * count as u64);
+                        elapsed
+                    });
+                });
+            }
+        }
+    }
+    proof_verification.finish();
+
+    for ((scheme, count), measurements) in measurements {
+        record_results(
+            results::HALO2_RESULTS,
+            "halo2",
+            Some(&instances_variant(scheme, count)),
+            None,
+            name,
+            &measurements,
+        );
+    }
+}
+
+/// Messages hashed by the Keccak benches, rotated by `i` so that every instance hashes its own
+fn keccak_inputs(i: usize) -> Vec<Vec<u8>> {
+    let mut inputs = vec![
+        vec![],
+        (0u8..1).collect::<Vec<_>>(),
+        (0u8..135).collect::<Vec<_>>(),
+        (0u8..136).collect::<Vec<_>>(),
+        (0u8..200).collect::<Vec<_>>(),
+    ];
+    let len = inputs.len();
+    inputs.rotate_left(i % len);
+    inputs
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable. The circuits are
+    // the same as those of their own benches, every instance getting its own witness
+    let ks = k_range(&[10, 14]);
+
+    let repeats: HashMap<u32, usize> = ks
+        .iter()
+        .map(|&k| (k, IsZeroCircuit::<Fr>::repeats_for_k(k)))
+        .collect();
+    bench_multi_instance(
+        c,
+        "IsZero",
+        &ks,
+        |k, i| IsZeroCircuit::new(Value::known(Fr::from(i as u64)), repeats[&k]),
+        |_, _| vec![],
+    );
+
+    let inputs = RangeCircuit::<Fr>::bench_inputs();
+    let range_repeats: HashMap<u32, u32> = ks
+        .iter()
+        .map(|&k| (k, RangeCircuit::<Fr>::bench_repeats_for_k(k)))
+        .collect();
+    // The main gate has a single instance column, which this circuit leaves empty
+    bench_multi_instance(
+        c,
+        "Range",
+        &ks,
+        |k, _| RangeCircuit::new(inputs.clone(), range_repeats[&k]),
+        |_, _| vec![vec![]],
+    );
+
+    // One signature verification takes between 2^17 and 2^18 rows, so smaller sizes set in
+    // `K_RANGE` are skipped. Every instance verifies its own signature
+    let ecdsa_ks: Vec<u32> = k_range(&[18]).into_iter().filter(|&k| k >= 18).collect();
+    let max_count = instance_counts().into_iter().max().unwrap_or(0);
+    let signatures: Vec<EcdsaVerifyCircuit<Secp256k1, Fr>> = (0..max_count)
+        .map(|_| EcdsaVerifyCircuit::random())
+        .collect();
+    bench_multi_instance(
+        c,
+        "ECDSA",
+        &ecdsa_ks,
+        |_, i| signatures[i].clone(),
+        |_, _| vec![vec![]],
+    );
+
+    // The Keccak circuits need at least 2^8 (bit) and 2^9 (packed) rows for the inputs above
+    let keccak_bit_ks: Vec<u32> = k_range(&[8]).into_iter().filter(|&k| k >= 8).collect();
+    bench_multi_instance(
+        c,
+        "Keccak bit",
+        &keccak_bit_ks,
+        |k, i| {
+            let mut circuit = KeccakBitCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&keccak_inputs(i));
+            circuit
+        },
+        |_, _| vec![],
+    );
+    let keccak_packed_ks: Vec<u32> = k_range(&[9]).into_iter().filter(|&k| k >= 9).collect();
+    bench_multi_instance(
+        c,
+        "Keccak packed",
+        &keccak_packed_ks,
+        |k, i| {
+            let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&keccak_inputs(i));
+            circuit
+        },
+        |_, _| vec![],
+    );
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
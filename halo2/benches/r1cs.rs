@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate criterion;
+use criterion::Criterion;
+
+use halo2::{
+    fit::max_repeats,
+    groth16::circuits::{iszero, repeated_less_than, LESS_THAN_BITS},
+    r1cs::R1cs,
+    r1cs_circuit::R1csCircuit,
+};
+use halo2_proofs::halo2curves::bn256::Fr as Fp;
+use std::collections::HashMap;
+
+mod harness;
+use harness::{bench_circuit_with_instances, k_range};
+
+/// Benches the R1CS built by `build(repeats)`, transpiled to Halo2, with as many repeats as fit in
+/// `2^k` rows for every `k` in `ks`
+fn bench_r1cs<B>(c: &mut Criterion, name: &str, ks: &[u32], build: B)
+where
+    B: Fn(usize) -> (R1cs<Fp>, Vec<Fp>),
+{
+    let transpile = |repeats| {
+        let (r1cs, witness) = build(repeats);
+        let circuit = R1csCircuit::new(r1cs, witness);
+        let instances = circuit.instances();
+        (circuit, instances)
+    };
+
+    // Fit, and build, the circuit of each size once
+    let circuits: HashMap<u32, (R1csCircuit<Fp>, Vec<Vec<Fp>>)> = ks
+        .iter()
+        .map(|&k| (k, transpile(max_repeats(k, transpile))))
+        .collect();
+
+    bench_circuit_with_instances(
+        c,
+        name,
+        ks,
+        |k| circuits[&k].0.clone(),
+        |k| circuits[&k].1.clone(),
+    );
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // Circuit sizes to bench, overridden by the `K_RANGE` environment variable
+    let ks = k_range(&[10, 14]);
+
+    // The R1CS ports of `iszero.circom` and `range.circom`, with the inputs of their `input.json`
+    bench_r1cs(c, "IsZero R1CS", &ks, |repeats| {
+        iszero(Fp::from(2), repeats)
+    });
+    bench_r1cs(c, "RepeatedLessThan R1CS", &ks, |repeats| {
+        repeated_less_than(2, 3, repeats, LESS_THAN_BITS)
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
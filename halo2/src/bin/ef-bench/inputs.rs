@@ -0,0 +1,154 @@
+//! JSON inputs of the circuits, shaped after the `input.json` files of the circom circuits so that
+//! the same file drives both proving systems. Every field is optional, missing ones taking the
+//! values the benches use.
+
+use ff::PrimeField;
+use halo2::{
+    groth16::circuits::LESS_THAN_BITS,
+    range::{RangeCircuit, RangeInput as RangeValue, LIMB_BIT_LEN},
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
+use serde::Deserialize;
+
+/// A field element, written as a JSON number or, when it doesn't fit in one, a decimal string
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Number {
+    Integer(u64),
+    Decimal(String),
+}
+
+impl Number {
+    pub fn to_field<F: PrimeField>(&self) -> Result<F, String> {
+        match self {
+            Number::Integer(value) => Ok(F::from(*value)),
+            Number::Decimal(value) => F::from_str_vartime(value)
+                .ok_or_else(|| format!("{} is not a decimal field element", value)),
+        }
+    }
+}
+
+/// A single value, or an array holding it as in `groth16/iszero/input.json`
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(Number),
+    Many(Vec<Number>),
+}
+
+/// Input of `iszero.circom`
+#[derive(Debug, Deserialize)]
+pub struct IsZeroInput {
+    #[serde(rename = "in")]
+    pub value: OneOrMany,
+}
+
+impl Default for IsZeroInput {
+    fn default() -> Self {
+        Self {
+            value: OneOrMany::One(Number::Integer(2)),
+        }
+    }
+}
+
+impl IsZeroInput {
+    pub fn value(&self) -> Result<&Number, String> {
+        match &self.value {
+            OneOrMany::One(value) => Ok(value),
+            OneOrMany::Many(values) if values.len() == 1 => Ok(&values[0]),
+            OneOrMany::Many(_) => Err("`in` should hold a single value".to_string()),
+        }
+    }
+}
+
+/// A value range checked by the Halo2 range circuit, decomposed into limbs of [`LIMB_BIT_LEN`]
+/// bits
+#[derive(Debug, Deserialize)]
+pub struct RangeCheck {
+    pub value: Number,
+    pub bit_len: usize,
+}
+
+/// Input of `range.circom`, whose `in` holds the two values compared by both less-than circuits,
+/// along with the values range checked by the Halo2 maingate range circuit
+#[derive(Debug, Deserialize)]
+pub struct RangeInput {
+    #[serde(rename = "in", default = "default_less_than")]
+    pub less_than: [u64; 2],
+    #[serde(default)]
+    pub ranges: Option<Vec<RangeCheck>>,
+}
+
+fn default_less_than() -> [u64; 2] {
+    [2, 3]
+}
+
+impl Default for RangeInput {
+    fn default() -> Self {
+        Self {
+            less_than: default_less_than(),
+            ranges: None,
+        }
+    }
+}
+
+impl RangeInput {
+    /// The two values of `in`, checked to fit in the [`LESS_THAN_BITS`] bits of the comparison
+    pub fn less_than(&self) -> Result<[u64; 2], String> {
+        let [a, b] = self.less_than;
+        if a >= 1 << LESS_THAN_BITS || b >= 1 << LESS_THAN_BITS {
+            return Err(format!(
+                "`in` should hold values of {} bits",
+                LESS_THAN_BITS
+            ));
+        }
+        Ok([a, b])
+    }
+
+    /// Inputs of the Halo2 range circuit, [`RangeCircuit::bench_inputs`] when `ranges` is missing
+    pub fn ranges(&self) -> Result<Vec<RangeValue<Fr>>, String> {
+        match &self.ranges {
+            Some(ranges) => ranges
+                .iter()
+                .map(|range| {
+                    Ok(RangeValue::new(
+                        Value::known(range.value.to_field()?),
+                        LIMB_BIT_LEN,
+                        range.bit_len,
+                    ))
+                })
+                .collect(),
+            None => Ok(RangeCircuit::bench_inputs()),
+        }
+    }
+}
+
+/// Secret key, message hash and nonce of the verified signature, random when missing
+#[derive(Debug, Default, Deserialize)]
+pub struct EcdsaInput {
+    pub sk: Option<Number>,
+    pub msg_hash: Option<Number>,
+    pub nonce: Option<Number>,
+}
+
+/// Messages hashed by the Keccak circuits
+#[derive(Debug, Default, Deserialize)]
+pub struct KeccakInput {
+    pub inputs: Option<Vec<Vec<u8>>>,
+}
+
+impl KeccakInput {
+    /// The messages, those of the Keccak benches when `inputs` is missing
+    pub fn inputs(&self) -> Vec<Vec<u8>> {
+        match &self.inputs {
+            Some(inputs) => inputs.clone(),
+            None => vec![
+                vec![],
+                (0u8..1).collect::<Vec<_>>(),
+                (0u8..135).collect::<Vec<_>>(),
+                (0u8..136).collect::<Vec<_>>(),
+                (0u8..200).collect::<Vec<_>>(),
+            ],
+        }
+    }
+}
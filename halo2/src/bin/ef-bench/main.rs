@@ -0,0 +1,634 @@
+//! Runs the benched circuits end to end from the command line, without editing bench sources.
+//!
+//! ```text
+//! ef-bench setup   <circuit> [--k K]
+//! ef-bench prove   <circuit> [--k K] [--input input.json] [--proof FILE] [--public FILE]
+//!                            [--multiopen gwc|shplonk] [--transcript blake2b|keccak256|poseidon]
+//! ef-bench verify  <circuit> [--k K] [--proof FILE] [--public FILE] [--multiopen gwc|shplonk]
+//!                            [--transcript blake2b|keccak256|poseidon]
+//! ef-bench compare <circuit> [--k K] [--input input.json] [--multiopen gwc|shplonk]
+//!                            [--transcript blake2b|keccak256|poseidon]
+//! ```
+//!
+//! `setup` generates the KZG parameters and verifying key of the circuit into the key cache (see
+//! `halo2::cache`), which `prove` and `verify` then reuse. `prove` writes the proof and the values
+//! of the instance columns to disk, and `verify` checks them. `compare` runs every phase of the
+//! circuit once on both proving systems (with every multi-open scheme and transcript unless
+//! `--multiopen` or `--transcript` is given) and prints the same table as `cargo run --bin report`;
+//! circuits without a native Groth16 port (the maingate range circuit, ECDSA and Keccak) are
+//! compared with the Groth16 results recorded by the circom scripts.
+
+use ff::PrimeField;
+use halo2::{
+    cache::KeyCache,
+    ecdsa::EcdsaVerifyCircuit,
+    fit::max_repeats,
+    groth16::{
+        self,
+        circuits::{
+            iszero, iszero_repeats_for_k, less_than_repeats_for_k, repeated_less_than,
+            LESS_THAN_BITS,
+        },
+    },
+    iszero::IsZeroPublicCircuit,
+    layout,
+    less_than::RepeatedLessThanCircuit,
+    memory::{self, CountingAllocator},
+    prover::{
+        instance_refs, prove_with_transcript, variant_name, verify_with_transcript, MultiOpen,
+    },
+    r1cs::R1cs,
+    range::RangeCircuit,
+    results::{self, format_ms, Machine, Phase, PhaseResult, GROTH16_RESULTS},
+    transcript::TranscriptHash,
+};
+use halo2_proofs::{
+    circuit::Value,
+    halo2curves::bn256::{Bn256, Fr},
+    plonk::{keygen_pk, keygen_vk, Circuit},
+    poly::kzg::commitment::ParamsKZG,
+};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use num_bigint::BigUint;
+use rand_core::OsRng;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process, slice,
+    time::Instant,
+};
+use zkevm_circuits::keccak_circuit::{
+    keccak_bit::KeccakBitCircuit, keccak_packed::KeccakPackedCircuit,
+};
+
+mod inputs;
+use inputs::{EcdsaInput, IsZeroInput, KeccakInput, RangeInput};
+
+/// Counts allocations, so that `compare` can report the memory used by every phase
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+usage: ef-bench <command> <circuit> [options]
+
+commands:
+    setup      generate and cache the parameters and verifying key
+    prove      prove the circuit, writing the proof and its public inputs
+    verify     verify a proof written by `prove`
+    compare    run the circuit once on Halo2 and Groth16 and print a comparison
+
+circuits: iszero, range, repeated-less-than, ecdsa, keccak-bit, keccak-packed
+
+options:
+    --k K           circuit size, defaulting to the one of the circuit's bench
+    --input FILE    JSON input, shaped after the circom input.json of the circuit
+    --proof FILE    proof file, <circuit>-<k>.proof by default
+    --public FILE   public inputs file, <circuit>-<k>.public.json by default
+    --multiopen S   multi-open scheme of the proof, gwc (the default) or shplonk
+    --transcript T  transcript of the proof, blake2b (the default), keccak256 or poseidon";
+
+/// Native Groth16 field, which is a different type from the Halo2 one
+type GrothFr = pairing::bn256::Fr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Command {
+    Setup,
+    Prove,
+    Verify,
+    Compare,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CircuitKind {
+    IsZero,
+    Range,
+    RepeatedLessThan,
+    Ecdsa,
+    KeccakBit,
+    KeccakPacked,
+}
+
+impl CircuitKind {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "iszero" => Some(Self::IsZero),
+            "range" => Some(Self::Range),
+            "repeated-less-than" => Some(Self::RepeatedLessThan),
+            "ecdsa" => Some(Self::Ecdsa),
+            "keccak-bit" => Some(Self::KeccakBit),
+            "keccak-packed" => Some(Self::KeccakPacked),
+            _ => None,
+        }
+    }
+
+    /// Name of the circuit in the bench results
+    fn name(&self) -> &'static str {
+        match self {
+            Self::IsZero => "IsZero public",
+            Self::Range => "Range",
+            Self::RepeatedLessThan => "RepeatedLessThan",
+            Self::Ecdsa => "ECDSA",
+            Self::KeccakBit => "Keccak bit",
+            Self::KeccakPacked => "Keccak packed",
+        }
+    }
+
+    /// Name of the circuit on the command line and in file names
+    fn slug(&self) -> &'static str {
+        match self {
+            Self::IsZero => "iszero",
+            Self::Range => "range",
+            Self::RepeatedLessThan => "repeated-less-than",
+            Self::Ecdsa => "ecdsa",
+            Self::KeccakBit => "keccak-bit",
+            Self::KeccakPacked => "keccak-packed",
+        }
+    }
+
+    /// Size the circuit is benched at by default
+    fn default_k(&self) -> u32 {
+        match self {
+            Self::IsZero | Self::Range | Self::RepeatedLessThan => 10,
+            Self::Ecdsa => 18,
+            Self::KeccakBit => 8,
+            Self::KeccakPacked => 9,
+        }
+    }
+}
+
+struct Args {
+    command: Command,
+    circuit: CircuitKind,
+    k: u32,
+    input: Option<PathBuf>,
+    proof: PathBuf,
+    public: PathBuf,
+    multiopen: Option<MultiOpen>,
+    transcript: Option<TranscriptHash>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = match args.next().as_deref() {
+        Some("setup") => Command::Setup,
+        Some("prove") => Command::Prove,
+        Some("verify") => Command::Verify,
+        Some("compare") => Command::Compare,
+        Some(command) => return Err(format!("unknown command {}", command)),
+        None => return Err("missing command".to_string()),
+    };
+    let circuit = args.next().ok_or("missing circuit")?;
+    let circuit =
+        CircuitKind::parse(&circuit).ok_or_else(|| format!("unknown circuit {}", circuit))?;
+
+    let (mut k, mut input, mut proof, mut public) = (None, None, None, None);
+    let (mut multiopen, mut transcript) = (None, None);
+    while let Some(option) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value of {}", option))?;
+        match option.as_str() {
+            "--k" => k = Some(value.parse().map_err(|_| format!("invalid k {}", value))?),
+            "--input" => input = Some(PathBuf::from(value)),
+            "--proof" => proof = Some(PathBuf::from(value)),
+            "--public" => public = Some(PathBuf::from(value)),
+            "--multiopen" => {
+                multiopen = Some(
+                    MultiOpen::parse(&value)
+                        .ok_or_else(|| format!("unknown multi-open scheme {}", value))?,
+                )
+            }
+            "--transcript" => {
+                transcript = Some(
+                    TranscriptHash::parse(&value)
+                        .ok_or_else(|| format!("unknown transcript {}", value))?,
+                )
+            }
+            _ => return Err(format!("unknown option {}", option)),
+        }
+    }
+
+    let k = k.unwrap_or_else(|| circuit.default_k());
+    Ok(Args {
+        command,
+        circuit,
+        k,
+        input,
+        proof: proof.unwrap_or_else(|| format!("{}-{}.proof", circuit.slug(), k).into()),
+        public: public.unwrap_or_else(|| format!("{}-{}.public.json", circuit.slug(), k).into()),
+        multiopen,
+        transcript,
+    })
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+/// Reads the input of the circuit, or its default one when `--input` isn't given
+fn read_input<T: Default + serde::de::DeserializeOwned>(args: &Args) -> Result<T, String> {
+    match &args.input {
+        Some(path) => {
+            let json = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path.display(), e))
+        }
+        None => Ok(T::default()),
+    }
+}
+
+/// Builds the circuit of `args` and runs its command
+fn run(args: &Args) -> Result<(), String> {
+    let k = args.k;
+    match args.circuit {
+        CircuitKind::IsZero => {
+            let input: IsZeroInput = read_input(args)?;
+            let value = input.value()?;
+            // `iszero.circom` exposes its outputs, but not its input
+            let repeats = IsZeroPublicCircuit::<Fr>::repeats_for_k(k, false);
+            let circuit =
+                IsZeroPublicCircuit::<Fr>::new(Value::known(value.to_field()?), repeats, false);
+            let instances = IsZeroPublicCircuit::<Fr>::instances(value.to_field()?, repeats, false);
+            let a: GrothFr = value.to_field()?;
+            let groth16 = move || iszero(a, iszero_repeats_for_k(k));
+            run_circuit(args, circuit, instances, Some(&groth16))
+        }
+        CircuitKind::Range => {
+            let input: RangeInput = read_input(args)?;
+            let ranges = input.ranges()?;
+            let repeats = max_repeats(k, |repeats| {
+                (
+                    RangeCircuit::<Fr>::new(ranges.clone(), repeats as u32),
+                    vec![vec![]],
+                )
+            });
+            if repeats == 0 {
+                return Err(format!("the range checks don't fit in 2^{} rows", k));
+            }
+            let circuit = RangeCircuit::new(ranges, repeats as u32);
+            // The main gate has a single instance column, which this circuit leaves empty
+            run_circuit(args, circuit, vec![vec![]], None)
+        }
+        CircuitKind::RepeatedLessThan => {
+            let input: RangeInput = read_input(args)?;
+            let [a, b] = input.less_than()?;
+            let circuit = RepeatedLessThanCircuit::<Fr, LESS_THAN_BITS>::new(
+                Value::known(Fr::from(a)),
+                Value::known(Fr::from(b)),
+                RepeatedLessThanCircuit::<Fr, LESS_THAN_BITS>::repeats_for_k(k),
+            );
+            let instances = RepeatedLessThanCircuit::<Fr, LESS_THAN_BITS>::instances(a, b);
+            let groth16 = move || {
+                repeated_less_than(
+                    a,
+                    b,
+                    less_than_repeats_for_k(k, LESS_THAN_BITS),
+                    LESS_THAN_BITS,
+                )
+            };
+            run_circuit(args, circuit, instances, Some(&groth16))
+        }
+        CircuitKind::Ecdsa => {
+            let input: EcdsaInput = read_input(args)?;
+            let circuit = match (&input.sk, &input.msg_hash, &input.nonce) {
+                (None, None, None) => EcdsaVerifyCircuit::<Secp256k1, Fr>::random(),
+                (Some(sk), Some(msg_hash), Some(nonce)) => EcdsaVerifyCircuit::sign(
+                    sk.to_field()?,
+                    msg_hash.to_field()?,
+                    nonce.to_field()?,
+                ),
+                _ => return Err("sk, msg_hash and nonce should all be given or none".to_string()),
+            };
+            run_circuit(args, circuit, vec![vec![]], None)
+        }
+        CircuitKind::KeccakBit => {
+            let input: KeccakInput = read_input(args)?;
+            let mut circuit = KeccakBitCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&input.inputs());
+            run_circuit(args, circuit, vec![], None)
+        }
+        CircuitKind::KeccakPacked => {
+            let input: KeccakInput = read_input(args)?;
+            let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
+            circuit.generate_witness(&input.inputs());
+            run_circuit(args, circuit, vec![], None)
+        }
+    }
+}
+
+/// Runs the command of `args` on `circuit`, whose instance columns hold `instances`. `groth16`
+/// builds the native Groth16 port of the circuit, if it has one.
+fn run_circuit<C: Circuit<Fr>>(
+    args: &Args,
+    circuit: C,
+    instances: Vec<Vec<Fr>>,
+    groth16: Option<&dyn Fn() -> (R1cs<GrothFr>, Vec<GrothFr>)>,
+) -> Result<(), String> {
+    let (name, k) = (args.circuit.name(), args.k);
+    let multiopen = args.multiopen.unwrap_or(MultiOpen::Gwc);
+    let transcript = args.transcript.unwrap_or(TranscriptHash::Blake2b);
+    let cache = KeyCache::from_env();
+    let params = cache
+        .kzg_params(k)
+        .map_err(|e| format!("failed to get the k = {} parameters: {}", k, e))?;
+
+    match args.command {
+        Command::Setup => {
+            let start = Instant::now();
+            cache
+                .vk(name, &params, &circuit)
+                .map_err(|e| format!("key generation failed: {:?}", e))?;
+            println!(
+                "{} at k = {}: parameters and verifying key cached in {} ({})",
+                name,
+                k,
+                cache.dir().display(),
+                format_ms(elapsed_ms(start))
+            );
+        }
+        Command::Prove => {
+            let pk = cache
+                .pk(name, &params, &circuit)
+                .map_err(|e| format!("key generation failed: {:?}", e))?;
+            let instance_refs = instance_refs(&instances);
+            let start = Instant::now();
+            let proof = prove_with_transcript(
+                multiopen,
+                transcript,
+                &params,
+                &pk,
+                &[circuit],
+                &[instance_refs.as_slice()],
+            )
+            .map_err(|e| format!("proof generation failed: {:?}", e))?;
+            let elapsed = elapsed_ms(start);
+
+            fs::write(&args.proof, &proof)
+                .map_err(|e| format!("failed to write {}: {}", args.proof.display(), e))?;
+            write_public(&args.public, &instances)?;
+            println!(
+                "{} at k = {}: proved in {}, {} byte proof written to {} and public inputs to {}",
+                name,
+                k,
+                format_ms(elapsed),
+                proof.len(),
+                args.proof.display(),
+                args.public.display()
+            );
+        }
+        Command::Verify => {
+            let vk = cache
+                .vk(name, &params, &circuit)
+                .map_err(|e| format!("key generation failed: {:?}", e))?;
+            let proof = fs::read(&args.proof)
+                .map_err(|e| format!("failed to read {}: {}", args.proof.display(), e))?;
+            let instances = read_public(&args.public)?;
+            let instance_refs = instance_refs(&instances);
+            let start = Instant::now();
+            verify_with_transcript(
+                multiopen,
+                transcript,
+                &params,
+                &vk,
+                &[instance_refs.as_slice()],
+                &proof,
+            )
+            .map_err(|e| format!("proof verification failed: {:?}", e))?;
+            println!(
+                "{} at k = {}: proof verified in {}",
+                name,
+                k,
+                format_ms(elapsed_ms(start))
+            );
+        }
+        Command::Compare => {
+            let schemes = match args.multiopen {
+                Some(multiopen) => vec![multiopen],
+                None => MultiOpen::ALL.to_vec(),
+            };
+            let transcripts = match args.transcript {
+                Some(transcript) => vec![transcript],
+                None => TranscriptHash::ALL.to_vec(),
+            };
+            let mut halo2 = vec![];
+            for scheme in schemes {
+                for &transcript in transcripts.iter() {
+                    halo2.extend(halo2_phases(
+                        scheme, transcript, name, k, &params, &circuit, &instances,
+                    )?);
+                }
+            }
+            let groth16 = match groth16 {
+                Some(build) => groth16_phases(name, k, build())?,
+                None => recorded_groth16(name, k)?,
+            };
+            print!("{}", results::report(&halo2, &groth16));
+        }
+    }
+    Ok(())
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+fn phase_result(
+    system: &str,
+    variant: Option<&str>,
+    name: &str,
+    k: u32,
+    phase: Phase,
+    mean_ms: f64,
+) -> PhaseResult {
+    PhaseResult {
+        system: system.to_string(),
+        circuit: name.to_string(),
+        k,
+        phase,
+        variant: variant.map(str::to_string),
+        mean_ms,
+        proof_bytes: None,
+        instance_values: None,
+        vk_bytes: None,
+        pk_bytes: None,
+        srs_bytes: None,
+        rows_used: None,
+        memory: None,
+        threads: None,
+        machine: Machine::current(),
+    }
+}
+
+/// Times every Halo2 phase of `circuit` once with the multi-open scheme `multiopen` and the
+/// transcript `transcript`, generating its keys from scratch, and measures the memory it uses, the
+/// sizes of the keys and of the proof and the number of rows the circuit uses
+fn halo2_phases<C: Circuit<Fr>>(
+    multiopen: MultiOpen,
+    transcript: TranscriptHash,
+    name: &str,
+    k: u32,
+    params: &ParamsKZG<Bn256>,
+    circuit: &C,
+    instances: &[Vec<Fr>],
+) -> Result<Vec<PhaseResult>, String> {
+    let instance_count = instances.iter().map(Vec::len).sum();
+    let instance_refs = instance_refs(instances);
+    let instances: &[&[&[Fr]]] = &[instance_refs.as_slice()];
+
+    let start = Instant::now();
+    let (vk, keygen_vk_memory) = memory::measure(|| keygen_vk(params, circuit));
+    let keygen_vk_ms = elapsed_ms(start);
+    let vk = vk.map_err(|e| format!("keygen_vk failed: {:?}", e))?;
+    let mut vk_bytes = vec![];
+    vk.write(&mut vk_bytes)
+        .map_err(|e| format!("failed to serialize the verifying key: {}", e))?;
+
+    let start = Instant::now();
+    let (pk, keygen_pk_memory) = memory::measure(|| keygen_pk(params, vk, circuit));
+    let keygen_pk_ms = elapsed_ms(start);
+    let pk = pk.map_err(|e| format!("keygen_pk failed: {:?}", e))?;
+
+    let start = Instant::now();
+    let (proof, prove_memory) = memory::measure(|| {
+        prove_with_transcript(
+            multiopen,
+            transcript,
+            params,
+            &pk,
+            slice::from_ref(circuit),
+            instances,
+        )
+    });
+    let prove_ms = elapsed_ms(start);
+    let proof = proof.map_err(|e| format!("proof generation failed: {:?}", e))?;
+
+    let start = Instant::now();
+    let (verified, verify_memory) = memory::measure(|| {
+        verify_with_transcript(
+            multiopen,
+            transcript,
+            params,
+            pk.get_vk(),
+            instances,
+            &proof,
+        )
+    });
+    let verify_ms = elapsed_ms(start);
+    verified.map_err(|e| format!("proof verification failed: {:?}", e))?;
+
+    let variant = variant_name(multiopen, transcript);
+    let variant = Some(variant.as_str());
+    let mut keygen_vk = phase_result("halo2", variant, name, k, Phase::KeygenVk, keygen_vk_ms);
+    keygen_vk.vk_bytes = Some(vk_bytes.len());
+    keygen_vk.rows_used = Some(layout::rows_used(circuit));
+    keygen_vk.memory = Some(keygen_vk_memory);
+    let mut keygen_pk = phase_result("halo2", variant, name, k, Phase::KeygenPk, keygen_pk_ms);
+    keygen_pk.memory = Some(keygen_pk_memory);
+    let mut prove = phase_result("halo2", variant, name, k, Phase::Prove, prove_ms);
+    prove.proof_bytes = Some(proof.len());
+    prove.instance_values = Some(instance_count);
+    prove.memory = Some(prove_memory);
+    let mut verify = phase_result("halo2", variant, name, k, Phase::Verify, verify_ms);
+    verify.memory = Some(verify_memory);
+    Ok(vec![keygen_vk, keygen_pk, prove, verify])
+}
+
+/// Times every phase of the native Groth16 prover on `r1cs` once, and measures the memory it uses
+/// and the sizes of the keys and of the proof
+fn groth16_phases(
+    name: &str,
+    k: u32,
+    (r1cs, witness): (R1cs<GrothFr>, Vec<GrothFr>),
+) -> Result<Vec<PhaseResult>, String> {
+    let start = Instant::now();
+    let (pk, setup_memory) = memory::measure(|| groth16::setup(&r1cs, OsRng));
+    let setup_ms = elapsed_ms(start);
+
+    let start = Instant::now();
+    let (proof, prove_memory) = memory::measure(|| groth16::prove(&pk, &r1cs, &witness, OsRng));
+    let prove_ms = elapsed_ms(start);
+
+    let pvk = groth16::prepare_verifying_key(&pk.vk);
+    let public_inputs = r1cs.public_inputs(&witness);
+    let start = Instant::now();
+    let (verified, verify_memory) =
+        memory::measure(|| groth16::verify(&pvk, public_inputs, &proof));
+    let verify_ms = elapsed_ms(start);
+    verified.map_err(|e| format!("Groth16 proof verification failed: {:?}", e))?;
+
+    let mut setup = phase_result("groth16", None, name, k, Phase::Setup, setup_ms);
+    setup.vk_bytes = Some(pk.vk.to_bytes().len());
+    setup.pk_bytes = Some(pk.to_bytes().len());
+    setup.memory = Some(setup_memory);
+    let mut prove = phase_result("groth16", None, name, k, Phase::Prove, prove_ms);
+    prove.proof_bytes = Some(proof.to_bytes().len());
+    prove.instance_values = Some(public_inputs.len());
+    prove.memory = Some(prove_memory);
+    let mut verify = phase_result("groth16", None, name, k, Phase::Verify, verify_ms);
+    verify.memory = Some(verify_memory);
+    Ok(vec![setup, prove, verify])
+}
+
+/// Groth16 results of the circuit recorded by the circom scripts, for circuits without a native
+/// port
+fn recorded_groth16(name: &str, k: u32) -> Result<Vec<PhaseResult>, String> {
+    let path = results::results_dir().join(GROTH16_RESULTS);
+    let recorded: Vec<PhaseResult> = results::read(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?
+        .into_iter()
+        .filter(|result| result.circuit == name && result.k == k)
+        .collect();
+    if recorded.is_empty() {
+        eprintln!(
+            "{} has no native Groth16 port and no Groth16 results at k = {} in {}",
+            name,
+            k,
+            path.display()
+        );
+    }
+    Ok(recorded)
+}
+
+/// Writes the values of the instance columns as JSON arrays of decimal strings
+fn write_public(path: &Path, instances: &[Vec<Fr>]) -> Result<(), String> {
+    let columns: Vec<Vec<String>> = instances
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .map(|value| BigUint::from_bytes_le(value.to_repr().as_ref()).to_string())
+                .collect()
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&columns).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+/// Reads the values of the instance columns written by [`write_public`]
+fn read_public(path: &Path) -> Result<Vec<Vec<Fr>>, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let columns: Vec<Vec<String>> =
+        serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path.display(), e))?;
+    columns
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .map(|value| {
+                    Fr::from_str_vartime(value)
+                        .ok_or_else(|| format!("{} is not a decimal field element", value))
+                })
+                .collect()
+        })
+        .collect()
+}
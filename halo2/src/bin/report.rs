@@ -0,0 +1,33 @@
+//! Prints the markdown comparison of the recorded Halo2 and Groth16 bench results, followed by
+//! how they scale with the number of threads when they were recorded with several.
+//!
+//! `cargo run --bin report [results directory]`, the directory defaulting to the one the benches
+//! write to (see `halo2::results::results_dir`).
+
+use halo2::results::{self, GROTH16_RESULTS, HALO2_RESULTS};
+use std::{env, path::PathBuf};
+
+fn main() {
+    let dir = env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(results::results_dir);
+
+    let halo2 = match results::read(&dir.join(HALO2_RESULTS)) {
+        Ok(results) => results,
+        Err(e) => panic!("{:#?}", e),
+    };
+    let groth16 = match results::read(&dir.join(GROTH16_RESULTS)) {
+        Ok(results) => results,
+        Err(e) => panic!("{:#?}", e),
+    };
+
+    print!("{}", results::report(&halo2, &groth16));
+
+    // Results recorded with explicit thread pools, see `THREADS` in the bench harness
+    let scaling: Vec<_> = halo2.into_iter().chain(groth16).collect();
+    let scaling = results::scaling_report(&scaling);
+    if !scaling.is_empty() {
+        print!("## Thread scaling\n\n{}", scaling);
+    }
+}
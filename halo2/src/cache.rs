@@ -0,0 +1,340 @@
+//! On-disk cache of KZG parameters and verifying keys, so that benches and tools don't regenerate
+//! them on every run.
+//!
+//! Parameters are keyed by `k`. Verifying keys are keyed by the name of the circuit, `k`, a hash
+//! of the circuit's `ConstraintSystem`, a hash of what its `synthesize` commits to (the fixed
+//! values, the selectors and the copy constraints) and a hash of the parameters they were
+//! generated with, so a key is regenerated as soon as the `configure` or the `synthesize` of its
+//! circuit changes or it is used with other parameters.
+//!
+//! Verifying keys of circuits with selectors are never cached: `VerifyingKey::read` doesn't
+//! compress the selectors of the circuit into fixed columns the way `keygen_vk` does, so the key
+//! it reads back doesn't match the proofs of such a circuit. [`KeyCache::vk`] generates them on
+//! every call instead.
+//!
+//! Only parameters and verifying keys are cached. `halo2_proofs` can't serialise proving keys at
+//! this version, so [`KeyCache::pk`] builds them from the cached verifying key, which skips
+//! `keygen_vk` but runs `keygen_pk` on every call.
+//!
+//! Hashes are computed with the standard library's `DefaultHasher`, whose output may change
+//! between Rust releases: a new toolchain at worst regenerates the cache.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Value,
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{
+        keygen_pk, keygen_vk, Advice, Any, Assigned, Assignment, Challenge, Circuit, Column,
+        ConstraintSystem, Error, Fixed, FloorPlanner, Instance, ProvingKey, Selector, VerifyingKey,
+    },
+    poly::{
+        commitment::{Params, ParamsProver},
+        kzg::commitment::ParamsKZG,
+    },
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    env,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::circom::PtauFile;
+
+/// Environment variable overriding the directory of the cache
+pub const CACHE_DIR_VAR: &str = "HALO2_CACHE_DIR";
+/// Cache directory used when `HALO2_CACHE_DIR` isn't set, relative to the `halo2` crate
+pub const DEFAULT_CACHE_DIR: &str = "../cache";
+/// Environment variable holding the path of a `.ptau` file to read the KZG parameters from
+pub const PTAU_VAR: &str = "PTAU";
+
+/// Returns the directory of the cache
+pub fn cache_dir() -> PathBuf {
+    env::var(CACHE_DIR_VAR)
+        .unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string())
+        .into()
+}
+
+/// Hash of the constraint system configured by `C`, which changes whenever its `configure` does
+pub fn constraint_system_hash<C: Circuit<Fr>>() -> u64 {
+    let mut cs = ConstraintSystem::default();
+    C::configure(&mut cs);
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", cs.pinned()).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether the constraint system configured by `C` has selectors, whose verifying keys can't be
+/// read back at this version of `halo2_proofs`
+pub fn has_selectors<F: FieldExt, C: Circuit<F>>() -> bool {
+    let mut cs = ConstraintSystem::default();
+    C::configure(&mut cs);
+    cs.num_selectors() > 0
+}
+
+/// Hash of the fixed values, selectors and copy constraints `circuit` assigns when laid out by its
+/// floor planner, which with its constraint system make up what its verifying key commits to
+pub fn synthesis_hash<F: FieldExt, C: Circuit<F>>(circuit: &C) -> u64 {
+    let mut cs = ConstraintSystem::default();
+    let config = C::configure(&mut cs);
+    let mut hasher = SynthesisHasher(DefaultHasher::new());
+    C::FloorPlanner::synthesize(&mut hasher, circuit, config, cs.constants().clone())
+        .expect("laying out the circuit should not fail");
+    hasher.0.finish()
+}
+
+/// Hash of the serialisation of `params`
+pub fn params_hash(params: &ParamsKZG<Bn256>) -> u64 {
+    let mut writer = HashWriter(DefaultHasher::new());
+    params
+        .write(&mut writer)
+        .expect("writing to a hasher should not fail");
+    writer.0.finish()
+}
+
+/// Feeds everything written to it to a hasher
+struct HashWriter<H: Hasher>(H);
+
+impl<H: Hasher> Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Assignment hashing the fixed values, selectors and copy constraints of a circuit, in the order
+/// they are assigned, and ignoring its witness
+struct SynthesisHasher<H: Hasher>(H);
+
+impl<H: Hasher> SynthesisHasher<H> {
+    fn hash_value<F: FieldExt>(&mut self, value: Value<Assigned<F>>) {
+        // Fixed values are known at key generation, an unknown one hashes as nothing
+        value.map(|value| self.0.write(value.evaluate().to_repr().as_ref()));
+    }
+}
+
+impl<F: FieldExt, H: Hasher> Assignment<F> for SynthesisHasher<H> {
+    fn enter_region<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A, AR>(&mut self, _: A, selector: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        format!("{:?}", selector).hash(&mut self.0);
+        row.hash(&mut self.0);
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        _: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Fixed>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        format!("{:?}", column).hash(&mut self.0);
+        row.hash(&mut self.0);
+        self.hash_value(to().map(|value| value.into()));
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        format!("{:?} {:?}", left_column, right_column).hash(&mut self.0);
+        left_row.hash(&mut self.0);
+        right_row.hash(&mut self.0);
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        format!("{:?}", column).hash(&mut self.0);
+        row.hash(&mut self.0);
+        self.hash_value(to);
+        Ok(())
+    }
+
+    fn get_challenge(&self, _: Challenge) -> Value<F> {
+        Value::unknown()
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+/// A directory holding parameters and verifying keys.
+///
+/// Failing to read an entry regenerates it, and failing to write one only prints a warning, so
+/// the cache never makes a run fail.
+#[derive(Clone, Debug)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the directory set in `HALO2_CACHE_DIR`, or [`DEFAULT_CACHE_DIR`]
+    pub fn from_env() -> Self {
+        Self::new(cache_dir())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Random KZG parameters for circuits of `2^k` rows, generated on the first call only
+    pub fn params(&self, k: u32) -> ParamsKZG<Bn256> {
+        let path = self.dir.join(format!("params-{}.bin", k));
+        if let Ok(params) =
+            File::open(&path).and_then(|file| ParamsKZG::read(&mut BufReader::new(file)))
+        {
+            return params;
+        }
+
+        let params = ParamsKZG::<Bn256>::new(k);
+        self.store(&path, |writer| params.write(writer));
+        params
+    }
+
+    /// KZG parameters for circuits of `2^k` rows, read from the `.ptau` file set in `PTAU` if any
+    /// and random (see [`KeyCache::params`]) otherwise
+    pub fn kzg_params(&self, k: u32) -> io::Result<ParamsKZG<Bn256>> {
+        match env::var(PTAU_VAR) {
+            Ok(path) => PtauFile::open(Path::new(&path))?.params(k),
+            Err(_) => Ok(self.params(k)),
+        }
+    }
+
+    /// Verifying key of `circuit` under `params`, generated on the first call only and again
+    /// whenever the constraint system or the synthesis of `circuit` changes. `name` keeps the keys
+    /// of different circuits apart in the cache directory.
+    ///
+    /// The key of a circuit with selectors is generated on every call (see [`has_selectors`]).
+    pub fn vk<C: Circuit<Fr>>(
+        &self,
+        name: &str,
+        params: &ParamsKZG<Bn256>,
+        circuit: &C,
+    ) -> Result<VerifyingKey<G1Affine>, Error> {
+        if has_selectors::<Fr, C>() {
+            return keygen_vk(params, circuit);
+        }
+
+        let path = self.dir.join(format!(
+            "{}-{}-{:016x}-{:016x}-{:016x}.vk",
+            file_name(name),
+            params.k(),
+            constraint_system_hash::<C>(),
+            synthesis_hash(circuit),
+            params_hash(params)
+        ));
+        if let Ok(vk) = File::open(&path)
+            .and_then(|file| VerifyingKey::read::<_, C>(&mut BufReader::new(file), params))
+        {
+            return Ok(vk);
+        }
+
+        let vk = keygen_vk(params, circuit)?;
+        self.store(&path, |writer| vk.write(writer));
+        Ok(vk)
+    }
+
+    /// Proving key of `circuit` under `params`, built by `keygen_pk` on every call from its cached
+    /// verifying key
+    pub fn pk<C: Circuit<Fr>>(
+        &self,
+        name: &str,
+        params: &ParamsKZG<Bn256>,
+        circuit: &C,
+    ) -> Result<ProvingKey<G1Affine>, Error> {
+        let vk = self.vk(name, params, circuit)?;
+        keygen_pk(params, vk, circuit)
+    }
+
+    /// Writes an entry through a temporary file, so that an interrupted run can't leave a
+    /// truncated entry behind
+    fn store(&self, path: &Path, write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>) {
+        let temporary = path.with_extension("tmp");
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| {
+                let mut writer = BufWriter::new(File::create(&temporary)?);
+                write(&mut writer)?;
+                writer.flush()
+            })
+            .and_then(|_| fs::rename(&temporary, path));
+        if let Err(e) = result {
+            eprintln!("failed to cache {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// `name` with everything but letters, digits, `-` and `_` replaced by `_`
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
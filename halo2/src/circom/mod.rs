@@ -0,0 +1,137 @@
+//! Readers for the binary files produced by circom and snarkjs.
+//!
+//! They all share iden3's container format: a 4 byte magic string, a version, and a list of
+//! sections, each starting with its type and size. Integers are little endian and field elements
+//! are stored as little endian integers of the size given in the file's header.
+
+use ff::PrimeField;
+use std::{
+    collections::HashMap,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+pub mod ptau;
+pub mod r1cs;
+pub mod wtns;
+
+pub use self::{ptau::PtauFile, r1cs::R1csFile, wtns::WtnsFile};
+
+/// Builds the error returned for malformed files
+pub(crate) fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// An iden3 binary file, with the position of each of its sections
+pub(crate) struct BinFile<R: Read + Seek> {
+    reader: R,
+    pub version: u32,
+    /// Start and size of every section, by type
+    sections: HashMap<u32, (u64, u64)>,
+}
+
+impl<R: Read + Seek> BinFile<R> {
+    /// Reads the header and section table of a file whose magic string should be `magic`
+    pub fn new(mut reader: R, magic: &[u8; 4]) -> io::Result<Self> {
+        let mut found = [0u8; 4];
+        reader.read_exact(&mut found)?;
+        if &found != magic {
+            return Err(invalid_data(format!(
+                "expected a {} file, found magic {:?}",
+                String::from_utf8_lossy(magic),
+                found
+            )));
+        }
+        let version = read_u32(&mut reader)?;
+
+        let mut sections = HashMap::new();
+        for _ in 0..read_u32(&mut reader)? {
+            let section_type = read_u32(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            let start = reader.stream_position()?;
+            if sections.insert(section_type, (start, size)).is_some() {
+                return Err(invalid_data(format!("duplicate section {}", section_type)));
+            }
+            reader.seek(SeekFrom::Start(start + size))?;
+        }
+
+        Ok(Self {
+            reader,
+            version,
+            sections,
+        })
+    }
+
+    pub fn has_section(&self, section_type: u32) -> bool {
+        self.sections.contains_key(&section_type)
+    }
+
+    /// Returns a reader over the content of a section
+    pub fn section(&mut self, section_type: u32) -> io::Result<io::Take<&mut R>> {
+        let (start, size) = *self
+            .sections
+            .get(&section_type)
+            .ok_or_else(|| invalid_data(format!("missing section {}", section_type)))?;
+        self.reader.seek(SeekFrom::Start(start))?;
+        Ok((&mut self.reader).take(size))
+    }
+}
+
+pub(crate) fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads `size` bytes, e.g. the prime of a header
+pub(crate) fn read_bytes(reader: &mut impl Read, size: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0u8; size];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads a field element stored as a little endian integer of `size` bytes
+pub(crate) fn read_field<F: PrimeField>(reader: &mut impl Read, size: usize) -> io::Result<F> {
+    let bytes = read_bytes(reader, size)?;
+    field_from_le_bytes(&bytes).ok_or_else(|| invalid_data("field element out of range"))
+}
+
+/// Converts a little endian integer into a field element, if it is smaller than the modulus.
+/// Relies on `F` representing its elements in little endian, as BN254's fields do.
+pub(crate) fn field_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    let mut repr = F::Repr::default();
+    let len = repr.as_ref().len();
+    if bytes.len() > len && bytes[len..].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    let used = bytes.len().min(len);
+    repr.as_mut()[..used].copy_from_slice(&bytes[..used]);
+    Option::from(F::from_repr(repr))
+}
+
+/// Checks that `prime`, a little endian integer, is the modulus of `F`
+pub(crate) fn check_prime<F: PrimeField>(prime: &[u8]) -> io::Result<()> {
+    // p is the modulus exactly when p itself is out of range and p - 1 = -1
+    let mut prime_minus_one = prime.to_vec();
+    for byte in prime_minus_one.iter_mut() {
+        let (value, borrow) = byte.overflowing_sub(1);
+        *byte = value;
+        if !borrow {
+            break;
+        }
+    }
+    if field_from_le_bytes::<F>(prime).is_none()
+        && field_from_le_bytes::<F>(&prime_minus_one) == Some(-F::one())
+    {
+        Ok(())
+    } else {
+        Err(invalid_data(
+            "the file's prime is not the modulus of the field",
+        ))
+    }
+}
@@ -0,0 +1,170 @@
+//! Reader for snarkjs' `.ptau` powers of tau files, turning them into Halo2's KZG parameters so
+//! that both proving systems can be benched on the same trusted setup.
+//!
+//! A file of power `p` holds `[tau^i]_1` for `i < 2^(p + 1) - 1` in its tauG1 section and
+//! `[tau^i]_2` for `i < 2^p` in its tauG2 section. The parameters of a circuit of `2^k` rows are
+//! the first `2^k` of the former and the first two of the latter, so any `k <= p` can be read from
+//! the same file. Points are stored uncompressed, as the little endian Montgomery form of their
+//! coordinates, the point at infinity being all zeros.
+
+use ff::Field;
+use group::{prime::PrimeCurveAffine, GroupEncoding};
+use halo2_proofs::{
+    arithmetic::{g_to_lagrange, CurveAffine},
+    halo2curves::{
+        bn256::{Bn256, Fq, Fq2, G1Affine, G2Affine},
+        pairing::Engine,
+    },
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+};
+use rayon::prelude::*;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::Path,
+};
+
+use super::{check_prime, field_from_le_bytes, invalid_data, read_bytes, read_u32, BinFile};
+
+const HEADER: u32 = 1;
+const TAU_G1: u32 = 2;
+const TAU_G2: u32 = 3;
+
+/// A `.ptau` file, whose powers of tau are only read when building parameters
+pub struct PtauFile<R: Read + Seek> {
+    file: BinFile<R>,
+    pub version: u32,
+    /// Size in bytes of every coordinate
+    pub field_size: u32,
+    /// The file holds the powers of tau of circuits of up to `2^power` rows
+    pub power: u32,
+    /// Power of the ceremony the file comes from, which may be larger than `power`
+    pub ceremony_power: u32,
+}
+
+impl PtauFile<BufReader<File>> {
+    /// Opens the `.ptau` file at `path`
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> PtauFile<R> {
+    /// Reads the header of a `.ptau` file over BN254
+    pub fn read(reader: R) -> io::Result<Self> {
+        let mut file = BinFile::new(reader, b"ptau")?;
+
+        let mut section = file.section(HEADER)?;
+        let field_size = read_u32(&mut section)?;
+        check_prime::<Fq>(&read_bytes(&mut section, field_size as usize)?)?;
+        let power = read_u32(&mut section)?;
+        let ceremony_power = read_u32(&mut section)?;
+
+        Ok(Self {
+            version: file.version,
+            file,
+            field_size,
+            power,
+            ceremony_power,
+        })
+    }
+
+    /// Builds the KZG parameters of circuits of `2^k` rows from the powers of tau of the file,
+    /// checking that they are consistent
+    pub fn params(&mut self, k: u32) -> io::Result<ParamsKZG<Bn256>> {
+        if k > self.power {
+            return Err(invalid_data(format!(
+                "k = {} is larger than the power of the file, {}",
+                k, self.power
+            )));
+        }
+        let size = self.field_size as usize;
+        let r_inv = montgomery_factor(size);
+
+        // At least two points, so that tau can be checked against tauG2 even for k = 0
+        let n = 1usize << k;
+        let bytes = read_bytes(&mut self.file.section(TAU_G1)?, n.max(2) * 2 * size)?;
+        let mut g = bytes
+            .par_chunks(2 * size)
+            .map(|point| read_g1(point, r_inv))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let bytes = read_bytes(&mut self.file.section(TAU_G2)?, 2 * 4 * size)?;
+        let g2 = read_g2(&bytes[..4 * size], r_inv)?;
+        let s_g2 = read_g2(&bytes[4 * size..], r_inv)?;
+
+        if g[0] != G1Affine::generator() || g2 != G2Affine::generator() {
+            return Err(invalid_data(
+                "the powers of tau don't start with the generators",
+            ));
+        }
+        // e([tau]_1, [1]_2) = e([1]_1, [tau]_2)
+        if Bn256::pairing(&g[1], &g2) != Bn256::pairing(&g[0], &s_g2) {
+            return Err(invalid_data("tauG1 and tauG2 don't hold the same tau"));
+        }
+        g.truncate(n);
+
+        let g_lagrange: Vec<G1Affine> =
+            g_to_lagrange(g.iter().map(|point| point.to_curve()).collect(), k);
+        params_from_parts(k, &g, &g_lagrange, g2, s_g2)
+    }
+}
+
+/// `ParamsKZG` can't be built from its parts directly, so they are serialised the way
+/// `ParamsKZG::write` does and read back
+fn params_from_parts(
+    k: u32,
+    g: &[G1Affine],
+    g_lagrange: &[G1Affine],
+    g2: G2Affine,
+    s_g2: G2Affine,
+) -> io::Result<ParamsKZG<Bn256>> {
+    let mut bytes = k.to_le_bytes().to_vec();
+    for point in g.iter().chain(g_lagrange.iter()) {
+        bytes.extend_from_slice(point.to_bytes().as_ref());
+    }
+    bytes.extend_from_slice(g2.to_bytes().as_ref());
+    bytes.extend_from_slice(s_g2.to_bytes().as_ref());
+    ParamsKZG::read(&mut bytes.as_slice())
+}
+
+/// Inverse of the Montgomery factor `2^(8 * size)`, taking coordinates out of Montgomery form
+fn montgomery_factor(size: usize) -> Fq {
+    Fq::from(2).pow_vartime([8 * size as u64]).invert().unwrap()
+}
+
+fn read_coordinate(bytes: &[u8], r_inv: Fq) -> io::Result<Fq> {
+    field_from_le_bytes::<Fq>(bytes)
+        .map(|montgomery| montgomery * r_inv)
+        .ok_or_else(|| invalid_data("coordinate out of range"))
+}
+
+fn read_g1(bytes: &[u8], r_inv: Fq) -> io::Result<G1Affine> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G1Affine::identity());
+    }
+    let size = bytes.len() / 2;
+    let x = read_coordinate(&bytes[..size], r_inv)?;
+    let y = read_coordinate(&bytes[size..], r_inv)?;
+    Option::from(G1Affine::from_xy(x, y)).ok_or_else(|| invalid_data("point not on G1"))
+}
+
+fn read_g2(bytes: &[u8], r_inv: Fq) -> io::Result<G2Affine> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G2Affine::identity());
+    }
+    let size = bytes.len() / 4;
+    let coordinates = bytes
+        .chunks(size)
+        .map(|coordinate| read_coordinate(coordinate, r_inv))
+        .collect::<io::Result<Vec<_>>>()?;
+    let x = Fq2 {
+        c0: coordinates[0],
+        c1: coordinates[1],
+    };
+    let y = Fq2 {
+        c0: coordinates[2],
+        c1: coordinates[3],
+    };
+    Option::from(G2Affine::from_xy(x, y)).ok_or_else(|| invalid_data("point not on G2"))
+}
@@ -0,0 +1,188 @@
+//! Reader for circom's `.r1cs` files, as produced by `circom --r1cs`.
+//!
+//! The format is described in iden3's `r1csfile` repository: a header section, a constraints
+//! section, a wire to label section and, for circuits using `pragma custom_templates`, two
+//! sections listing the custom gates and where they are applied.
+
+use ff::PrimeField;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::Path,
+};
+
+use super::{check_prime, invalid_data, read_bytes, read_field, read_u32, read_u64, BinFile};
+use crate::r1cs::{Constraint, LinearCombination, R1cs};
+
+const HEADER: u32 = 1;
+const CONSTRAINTS: u32 = 2;
+const WIRE_TO_LABEL: u32 = 3;
+const CUSTOM_GATES_LIST: u32 = 4;
+const CUSTOM_GATES_APPLICATION: u32 = 5;
+
+/// The header section of a `.r1cs` file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// Size in bytes of every field element
+    pub field_size: u32,
+    /// Modulus of the field, as a little endian integer
+    pub prime: Vec<u8>,
+    /// Number of wires, including the constant one
+    pub num_wires: u32,
+    pub num_pub_out: u32,
+    pub num_pub_in: u32,
+    pub num_prv_in: u32,
+    /// Number of signals of the circuit, including those the optimiser removed from the wires
+    pub num_labels: u64,
+    pub num_constraints: u32,
+}
+
+/// A custom template declared with `pragma custom_templates`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomGate<F: PrimeField> {
+    pub name: String,
+    pub parameters: Vec<F>,
+}
+
+/// An application of a custom gate to some signals
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomGateUse {
+    /// Index of the gate in [`R1csFile::custom_gates`]
+    pub gate: usize,
+    pub signals: Vec<u64>,
+}
+
+/// Content of a `.r1cs` file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csFile<F: PrimeField> {
+    pub version: u32,
+    pub header: Header,
+    pub r1cs: R1cs<F>,
+    pub custom_gates: Vec<CustomGate<F>>,
+    pub custom_gate_uses: Vec<CustomGateUse>,
+}
+
+impl<F: PrimeField> R1csFile<F> {
+    /// Reads the `.r1cs` file at `path`
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a `.r1cs` file, whose prime should be the modulus of `F`
+    pub fn read<R: Read + Seek>(reader: R) -> io::Result<Self> {
+        let mut file = BinFile::new(reader, b"r1cs")?;
+
+        let header = read_header(&mut file.section(HEADER)?)?;
+        check_prime::<F>(&header.prime)?;
+        let field_size = header.field_size as usize;
+
+        let mut section = file.section(CONSTRAINTS)?;
+        let constraints = (0..header.num_constraints)
+            .map(|_| {
+                Ok(Constraint {
+                    a: read_linear_combination(&mut section, field_size, header.num_wires)?,
+                    b: read_linear_combination(&mut section, field_size, header.num_wires)?,
+                    c: read_linear_combination(&mut section, field_size, header.num_wires)?,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut section = file.section(WIRE_TO_LABEL)?;
+        let wire_labels = (0..header.num_wires)
+            .map(|_| read_u64(&mut section))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut custom_gates = vec![];
+        if file.has_section(CUSTOM_GATES_LIST) {
+            let mut section = file.section(CUSTOM_GATES_LIST)?;
+            for _ in 0..read_u32(&mut section)? {
+                let name = read_string(&mut section)?;
+                let parameters = (0..read_u32(&mut section)?)
+                    .map(|_| read_field(&mut section, field_size))
+                    .collect::<io::Result<Vec<F>>>()?;
+                custom_gates.push(CustomGate { name, parameters });
+            }
+        }
+
+        let mut custom_gate_uses = vec![];
+        if file.has_section(CUSTOM_GATES_APPLICATION) {
+            let mut section = file.section(CUSTOM_GATES_APPLICATION)?;
+            for _ in 0..read_u32(&mut section)? {
+                let gate = read_u32(&mut section)? as usize;
+                if gate >= custom_gates.len() {
+                    return Err(invalid_data(format!("unknown custom gate {}", gate)));
+                }
+                let signals = (0..read_u32(&mut section)?)
+                    .map(|_| read_u64(&mut section))
+                    .collect::<io::Result<Vec<_>>>()?;
+                custom_gate_uses.push(CustomGateUse { gate, signals });
+            }
+        }
+
+        Ok(Self {
+            version: file.version,
+            r1cs: R1cs {
+                num_public: (header.num_pub_out + header.num_pub_in) as usize,
+                num_wires: header.num_wires as usize,
+                constraints,
+                wire_labels,
+            },
+            header,
+            custom_gates,
+            custom_gate_uses,
+        })
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.r1cs.num_constraints()
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.r1cs.num_wires
+    }
+}
+
+fn read_header(section: &mut impl Read) -> io::Result<Header> {
+    let field_size = read_u32(section)?;
+    Ok(Header {
+        field_size,
+        prime: read_bytes(section, field_size as usize)?,
+        num_wires: read_u32(section)?,
+        num_pub_out: read_u32(section)?,
+        num_pub_in: read_u32(section)?,
+        num_prv_in: read_u32(section)?,
+        num_labels: read_u64(section)?,
+        num_constraints: read_u32(section)?,
+    })
+}
+
+fn read_linear_combination<F: PrimeField>(
+    section: &mut impl Read,
+    field_size: usize,
+    num_wires: u32,
+) -> io::Result<LinearCombination<F>> {
+    let terms = (0..read_u32(section)?)
+        .map(|_| {
+            let wire = read_u32(section)?;
+            if wire >= num_wires {
+                return Err(invalid_data(format!("wire {} out of range", wire)));
+            }
+            Ok((wire as usize, read_field(section, field_size)?))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(LinearCombination { terms })
+}
+
+/// Reads a null terminated string
+fn read_string(section: &mut impl Read) -> io::Result<String> {
+    let mut bytes = vec![];
+    loop {
+        let mut byte = [0u8];
+        section.read_exact(&mut byte)?;
+        if byte[0] == 0 {
+            break;
+        }
+        bytes.push(byte[0]);
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_data("custom gate name is not UTF-8"))
+}
@@ -0,0 +1,51 @@
+//! Reader for the `.wtns` witness files written by the `generate_witness.js` scripts of circom.
+//!
+//! A header section holds the field's size and prime and the number of values, and a second
+//! section holds the value of every wire, starting with the constant one.
+
+use ff::PrimeField;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::Path,
+};
+
+use super::{check_prime, read_bytes, read_field, read_u32, BinFile};
+
+const HEADER: u32 = 1;
+const WITNESS: u32 = 2;
+
+/// Content of a `.wtns` file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WtnsFile<F: PrimeField> {
+    pub version: u32,
+    /// Value of every wire, in the order of the wires of the `.r1cs` file
+    pub witness: Vec<F>,
+}
+
+impl<F: PrimeField> WtnsFile<F> {
+    /// Reads the `.wtns` file at `path`
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a `.wtns` file, whose prime should be the modulus of `F`
+    pub fn read<R: Read + Seek>(reader: R) -> io::Result<Self> {
+        let mut file = BinFile::new(reader, b"wtns")?;
+
+        let mut section = file.section(HEADER)?;
+        let field_size = read_u32(&mut section)? as usize;
+        check_prime::<F>(&read_bytes(&mut section, field_size)?)?;
+        let num_values = read_u32(&mut section)?;
+
+        let mut section = file.section(WITNESS)?;
+        let witness = (0..num_values)
+            .map(|_| read_field(&mut section, field_size))
+            .collect::<io::Result<Vec<F>>>()?;
+
+        Ok(Self {
+            version: file.version,
+            witness,
+        })
+    }
+}
@@ -0,0 +1,300 @@
+use halo2wrong::halo2::{
+    arithmetic::{CurveAffine, FieldExt},
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error, FloorPlanner},
+};
+use rand_core::OsRng;
+
+use ::ecdsa::ecdsa::{AssignedEcdsaSig, AssignedPublicKey, EcdsaChip};
+use ecc::{integer::Range, EccConfig, GeneralEccChip};
+use group::{ff::Field, Curve, Group};
+use integer::IntegerInstructions;
+use maingate::{
+    big_to_fe, fe_to_big, MainGate, MainGateConfig, RangeChip, RangeConfig, RangeInstructions,
+    RegionCtx,
+};
+use std::marker::PhantomData;
+
+use crate::fit::max_repeats;
+
+pub const BIT_LEN_LIMB: usize = 68;
+pub const NUMBER_OF_LIMBS: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct EcdsaVerifyConfig {
+    main_gate_config: MainGateConfig,
+    range_config: RangeConfig,
+}
+
+impl EcdsaVerifyConfig {
+    pub fn new<C: CurveAffine, N: FieldExt>(meta: &mut ConstraintSystem<N>) -> Self {
+        let (rns_base, rns_scalar) = GeneralEccChip::<C, N, NUMBER_OF_LIMBS, BIT_LEN_LIMB>::rns();
+        let main_gate_config = MainGate::<N>::configure(meta);
+        let mut overflow_bit_lens: Vec<usize> = vec![];
+        overflow_bit_lens.extend(rns_base.overflow_lengths());
+        overflow_bit_lens.extend(rns_scalar.overflow_lengths());
+        let composition_bit_lens = vec![BIT_LEN_LIMB / NUMBER_OF_LIMBS];
+
+        let range_config = RangeChip::<N>::configure(
+            meta,
+            &main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+        );
+        EcdsaVerifyConfig {
+            main_gate_config,
+            range_config,
+        }
+    }
+
+    pub fn ecc_chip_config(&self) -> EccConfig {
+        EccConfig::new(self.range_config.clone(), self.main_gate_config.clone())
+    }
+
+    pub fn config_range<N: FieldExt>(&self, layouter: &mut impl Layouter<N>) -> Result<(), Error> {
+        let range_chip = RangeChip::<N>::new(self.range_config.clone());
+        range_chip.load_table(layouter)?;
+
+        Ok(())
+    }
+}
+
+/// Circuit verifying an ECDSA signature over the curve `E`, emulated in the native field `N`,
+/// `repeats` times in order to bench larger versions of the circuit, laid out by the floor planner
+/// `P`
+pub struct EcdsaVerifyCircuit<E: CurveAffine, N: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub public_key: Value<E>,
+    pub signature: Value<(E::Scalar, E::Scalar)>,
+    pub msg_hash: Value<E::Scalar>,
+
+    pub aux_generator: E,
+    pub window_size: usize,
+    pub repeats: usize,
+    _marker: PhantomData<N>,
+    _planner: PhantomData<P>,
+}
+
+impl<E: CurveAffine, N: FieldExt, P: FloorPlanner> EcdsaVerifyCircuit<E, N, P> {
+    /// The same circuit, verifying its signature `repeats` times
+    pub fn repeated(self, repeats: usize) -> Self {
+        Self { repeats, ..self }
+    }
+
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> EcdsaVerifyCircuit<E, N, Q> {
+        EcdsaVerifyCircuit {
+            public_key: self.public_key,
+            signature: self.signature,
+            msg_hash: self.msg_hash,
+            aux_generator: self.aux_generator,
+            window_size: self.window_size,
+            repeats: self.repeats,
+            _marker: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<E: CurveAffine, N: FieldExt, P: FloorPlanner> Clone for EcdsaVerifyCircuit<E, N, P> {
+    fn clone(&self) -> Self {
+        Self {
+            public_key: self.public_key,
+            signature: self.signature,
+            msg_hash: self.msg_hash,
+            aux_generator: self.aux_generator,
+            window_size: self.window_size,
+            repeats: self.repeats,
+            _marker: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<E: CurveAffine, N: FieldExt, P: FloorPlanner> Default for EcdsaVerifyCircuit<E, N, P> {
+    fn default() -> Self {
+        Self {
+            public_key: Value::default(),
+            signature: Value::default(),
+            msg_hash: Value::default(),
+            aux_generator: E::default(),
+            window_size: 0,
+            repeats: 0,
+            _marker: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<E: CurveAffine, N: FieldExt> EcdsaVerifyCircuit<E, N> {
+    pub fn new(
+        public_key: Value<E>,
+        signature: Value<(E::Scalar, E::Scalar)>,
+        msg_hash: Value<E::Scalar>,
+        aux_generator: E,
+        window_size: usize,
+    ) -> Self {
+        Self {
+            public_key,
+            signature,
+            msg_hash,
+            aux_generator,
+            window_size,
+            repeats: 1,
+            _marker: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Largest number of verifications of the signature of this circuit that fits in a table of
+    /// `2^k` rows, 0 below 2^18 rows as a single one takes more than 2^17
+    pub fn repeats_for_k(&self, k: u32) -> usize {
+        max_repeats(k, |repeats| (self.clone().repeated(repeats), vec![vec![]]))
+    }
+
+    /// Creates a circuit verifying a signature of a random message hash under a random key pair
+    pub fn random() -> Self {
+        // Generate a key pair
+        let sk = <E as CurveAffine>::ScalarExt::random(OsRng);
+
+        // Suppose `m_hash` is the message hash
+        let msg_hash = <E as CurveAffine>::ScalarExt::random(OsRng);
+
+        // Draw randomness
+        let k = <E as CurveAffine>::ScalarExt::random(OsRng);
+
+        Self::sign(sk, msg_hash, k)
+    }
+
+    /// Creates a circuit verifying the signature of `msg_hash` under the secret key `sk`, computed
+    /// with the nonce `k`
+    pub fn sign(sk: E::Scalar, msg_hash: E::Scalar, k: E::Scalar) -> Self {
+        let g = E::generator();
+        let k_inv = k.invert().unwrap();
+
+        // Calculate `r`
+        let r_point = (g * k).to_affine().coordinates().unwrap();
+        let x = r_point.x();
+        let r = mod_n::<E>(*x);
+
+        // Calculate `s`
+        let s = k_inv * (msg_hash + (r * sk));
+
+        Self::from_signature(sk, msg_hash, r, s)
+    }
+
+    /// Creates a circuit verifying the signature `(r, s)` of `msg_hash` under the secret key `sk`
+    ///
+    /// Panics if the signature is not valid.
+    pub fn from_signature(sk: E::Scalar, msg_hash: E::Scalar, r: E::Scalar, s: E::Scalar) -> Self {
+        // This function always returns the same generator, it is not random
+        let g = E::generator();
+
+        // Generate a key pair
+        let public_key = (g * sk).to_affine();
+
+        // Sanity check. Ensure we construct a valid signature. So lets verify it
+        {
+            let s_inv = s.invert().unwrap();
+            let u_1 = msg_hash * s_inv;
+            let u_2 = r * s_inv;
+            let r_point = ((g * u_1) + (public_key * u_2))
+                .to_affine()
+                .coordinates()
+                .unwrap();
+            let x_candidate = r_point.x();
+            let r_candidate = mod_n::<E>(*x_candidate);
+            assert_eq!(r, r_candidate);
+        }
+
+        let aux_generator = E::CurveExt::random(OsRng).to_affine();
+        Self::new(
+            Value::known(public_key),
+            Value::known((r, s)),
+            Value::known(msg_hash),
+            aux_generator,
+            2,
+        )
+    }
+}
+
+impl<E: CurveAffine, N: FieldExt, P: FloorPlanner> Circuit<N> for EcdsaVerifyCircuit<E, N, P> {
+    type Config = EcdsaVerifyConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            repeats: self.repeats,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<N>) -> Self::Config {
+        EcdsaVerifyConfig::new::<E, N>(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<N>,
+    ) -> Result<(), Error> {
+        let mut ecc_chip =
+            GeneralEccChip::<E, N, NUMBER_OF_LIMBS, BIT_LEN_LIMB>::new(config.ecc_chip_config());
+
+        layouter.assign_region(
+            || "assign aux values",
+            |region| {
+                let offset = 0;
+                let ctx = &mut RegionCtx::new(region, offset);
+
+                ecc_chip.assign_aux_generator(ctx, Value::known(self.aux_generator))?;
+                ecc_chip.assign_aux(ctx, self.window_size, 1)?;
+                Ok(())
+            },
+        )?;
+
+        let ecdsa_chip = EcdsaChip::new(ecc_chip.clone());
+        let scalar_chip = ecc_chip.scalar_field_chip();
+
+        for _ in 0..self.repeats {
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+
+                    let r = self.signature.map(|signature| signature.0);
+                    let s = self.signature.map(|signature| signature.1);
+                    let integer_r = ecc_chip.new_unassigned_scalar(r);
+                    let integer_s = ecc_chip.new_unassigned_scalar(s);
+                    let msg_hash = ecc_chip.new_unassigned_scalar(self.msg_hash);
+
+                    let r_assigned =
+                        scalar_chip.assign_integer(ctx, integer_r, Range::Remainder)?;
+                    let s_assigned =
+                        scalar_chip.assign_integer(ctx, integer_s, Range::Remainder)?;
+                    let sig = AssignedEcdsaSig {
+                        r: r_assigned,
+                        s: s_assigned,
+                    };
+
+                    let pk_in_circuit = ecc_chip.assign_point(ctx, self.public_key)?;
+                    let pk_assigned = AssignedPublicKey {
+                        point: pk_in_circuit,
+                    };
+                    let msg_hash = scalar_chip.assign_integer(ctx, msg_hash, Range::Remainder)?;
+                    ecdsa_chip.verify(ctx, &sig, &pk_assigned, &msg_hash)
+                },
+            )?;
+        }
+
+        config.config_range(&mut layouter)?;
+
+        Ok(())
+    }
+}
+
+/// Reduces a base field element of `C` into its scalar field
+pub fn mod_n<C: CurveAffine>(x: C::Base) -> C::Scalar {
+    let x_big = fe_to_big(x);
+    big_to_fe(x_big)
+}
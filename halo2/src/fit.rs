@@ -0,0 +1,80 @@
+//! Fitting of repeated circuits to a table of `2^k` rows.
+//!
+//! Benches compare Halo2 circuits of `2^k` rows against circom circuits of `2^k` constraints, so
+//! a circuit that repeats a computation should repeat it as many times as the rows allow. Rather
+//! than guessing that number, it is found by laying the circuit out with `MockProver`, which fails
+//! with `Error::NotEnoughRowsAvailable` once an assignment lands outside of the usable rows.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+/// Number of rows of a `2^k` row table that `C` can assign to; the remaining rows hold the
+/// blinding factors of the advice columns (plus one row used by the permutation argument)
+pub fn usable_rows<F: FieldExt, C: Circuit<F>>(k: u32) -> usize {
+    let mut cs = ConstraintSystem::default();
+    C::configure(&mut cs);
+    (1 << k) - (cs.blinding_factors() + 1)
+}
+
+/// Returns whether the circuit (with its instance columns) built by `build(repeats)` fits in
+/// `2^k` rows.
+///
+/// Panics if laying out the circuit fails for any other reason than a lack of rows.
+pub fn fits<F, C, B>(k: u32, repeats: usize, build: &B) -> bool
+where
+    F: FieldExt,
+    C: Circuit<F>,
+    B: Fn(usize) -> (C, Vec<Vec<F>>),
+{
+    let (circuit, instances) = build(repeats);
+    match MockProver::run(k, &circuit, instances) {
+        Ok(_) => true,
+        Err(Error::NotEnoughRowsAvailable { .. }) | Err(Error::InstanceTooLarge) => false,
+        Err(e) => panic!("{:#?}", e),
+    }
+}
+
+/// Searches for the largest number of repeats such that the circuit built by `build(repeats)`
+/// fits in `2^k` rows, returning 0 if not even one repeat fits.
+///
+/// Every repeat is assumed to assign at least one advice cell, which bounds the search by the
+/// number of usable cells of the advice columns (the usable rows when there is a single one).
+/// Circuits laying several repeats on a row, such as those of a wide `StandardPlonk`, fit more
+/// repeats than rows.
+pub fn max_repeats<F, C, B>(k: u32, build: B) -> usize
+where
+    F: FieldExt,
+    C: Circuit<F>,
+    B: Fn(usize) -> (C, Vec<Vec<F>>),
+{
+    let mut cs = ConstraintSystem::<F>::default();
+    C::configure(&mut cs);
+    let limit = usable_rows::<F, C>(k) * cs.num_advice_columns().max(1);
+    if !fits(k, 1, &build) {
+        return 0;
+    }
+
+    // Double the repeats until the circuit doesn't fit anymore...
+    let mut low = 1;
+    let mut high = 2;
+    while high <= limit && fits(k, high, &build) {
+        low = high;
+        high *= 2;
+    }
+    let mut high = high.min(limit + 1);
+
+    // ...then binary search between the last repeats that fit and the first that didn't
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(k, mid, &build) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
@@ -0,0 +1,112 @@
+//! R1CS equivalents of the circom circuits in `groth16/`, built wire for wire in the same order as
+//! circom so that their constraint systems match the compiled `.r1cs` files.
+
+use ff::PrimeField;
+
+use crate::r1cs::{LinearCombination, R1cs, R1csBuilder};
+
+/// Bit length of the values compared by the range bench, as in `range.circom`
+pub const LESS_THAN_BITS: usize = 8;
+
+/// `IsZero_Generic(repeats)` of `iszero.circom`: `repeats` IsZero checks of the private input
+/// `a`, each exposing its result as a public output.
+///
+/// Every repeat costs two constraints, `in * inv = 1 - out` and `in * out = 0`.
+pub fn iszero<F: PrimeField>(a: F, repeats: usize) -> (R1cs<F>, Vec<F>) {
+    let mut builder = R1csBuilder::new();
+    let inv: F = Option::from(a.invert()).unwrap_or_else(F::zero);
+    let out = F::one() - a * inv;
+
+    let outs: Vec<usize> = (0..repeats).map(|_| builder.public(out)).collect();
+    let a = builder.private(a);
+    for out in outs {
+        let inv = builder.private(inv);
+        builder.enforce(
+            LinearCombination::wire(a),
+            LinearCombination::wire(inv),
+            LinearCombination::constant(F::one()).add(out, -F::one()),
+        );
+        builder.enforce(
+            LinearCombination::wire(a),
+            LinearCombination::wire(out),
+            LinearCombination::zero(),
+        );
+    }
+
+    builder.build()
+}
+
+/// Largest number of repeats of [`iszero`] whose Groth16 evaluation domain has `2^k` points
+pub fn iszero_repeats_for_k(k: u32) -> usize {
+    // Two constraints and one public output per repeat, plus the constant one
+    ((1 << k) - 1) / 3
+}
+
+/// `RepeatedLessThan(repeats, n)` of `range.circom`: `repeats` checks of `a < b` for the private
+/// `n` bit inputs, the first of which is exposed as the public output.
+///
+/// As in circomlib's `LessThan`, `a + 2^n - b` is decomposed into `n + 1` bits whose top bit is
+/// set exactly when `a >= b`. The constraints are those circom's optimiser leaves: the linear one
+/// tying the bits back to `a + 2^n - b` is substituted into the boolean constraint of the lowest
+/// bit, which becomes `(a + 2^n - b - sum) * (a + 2^n - b - sum - 1) = 0` where `sum` holds the
+/// other bits and their weights. Every repeat costs `n + 1` constraints, the 9 per repeat noted in
+/// `range.circom` for `n = 8`.
+pub fn repeated_less_than<F: PrimeField>(
+    a: u64,
+    b: u64,
+    repeats: usize,
+    n: usize,
+) -> (R1cs<F>, Vec<F>) {
+    assert!(n > 0 && n < 63, "inputs should have between 1 and 62 bits");
+    assert!(a < 1 << n && b < 1 << n, "inputs should fit in {} bits", n);
+    let mut builder = R1csBuilder::new();
+    let shifted = a + (1 << n) - b;
+    let bits: Vec<F> = (0..=n).map(|i| F::from((shifted >> i) & 1)).collect();
+
+    let out = builder.public(F::one() - bits[n]);
+    let a = builder.private(F::from(a));
+    let b = builder.private(F::from(b));
+
+    let offset = F::from(1 << n);
+    for repeat in 0..repeats {
+        // The lowest bit, a + 2^n - b minus the weighted higher bits, has no wire of its own
+        let mut lowest_bit = LinearCombination::wire(a).add(0, offset).add(b, -F::one());
+        let mut power = F::one();
+        let mut top_bit = 0;
+        for value in bits.iter().skip(1) {
+            power = power.double();
+            let bit = builder.private(*value);
+            builder.enforce(
+                LinearCombination::wire(bit),
+                LinearCombination::wire(bit).add(0, -F::one()),
+                LinearCombination::zero(),
+            );
+            lowest_bit = lowest_bit.add(bit, -power);
+            top_bit = bit;
+        }
+        builder.enforce(
+            lowest_bit.clone(),
+            lowest_bit.add(0, -F::one()),
+            LinearCombination::zero(),
+        );
+
+        // out = 1 - bits[n], only exposed for the first repeat
+        if repeat == 0 {
+            builder.enforce(
+                LinearCombination::constant(F::one()).add(top_bit, -F::one()),
+                LinearCombination::constant(F::one()),
+                LinearCombination::wire(out),
+            );
+        }
+    }
+
+    builder.build()
+}
+
+/// Largest number of repeats of [`repeated_less_than`] with `n` bit inputs whose Groth16 evaluation
+/// domain has `2^k` points
+pub fn less_than_repeats_for_k(k: u32, n: usize) -> usize {
+    // Constraints of the repeats, plus the output constraint, the public output and the constant
+    // one
+    ((1 << k) - 3) / (n + 1)
+}
@@ -0,0 +1,173 @@
+//! Radix-2 evaluation domains, over which the QAP of an R1CS is interpolated.
+
+use ff::PrimeField;
+use rayon::prelude::*;
+
+/// The multiplicative subgroup of `size` roots of unity, `size` being a power of two
+#[derive(Clone, Debug)]
+pub struct EvaluationDomain<F: PrimeField> {
+    pub size: usize,
+    pub log_size: u32,
+    /// Generator of the domain
+    pub omega: F,
+    omega_inv: F,
+    size_inv: F,
+    /// Shift of the coset `coset * <omega>` used to divide by the vanishing polynomial
+    coset: F,
+    coset_inv: F,
+}
+
+impl<F: PrimeField> EvaluationDomain<F> {
+    /// Smallest domain holding at least `min_size` points
+    pub fn new(min_size: usize) -> Self {
+        let size = min_size.next_power_of_two();
+        let log_size = size.trailing_zeros();
+        assert!(
+            log_size <= F::S,
+            "the field has no domain of size 2^{}",
+            log_size
+        );
+
+        let mut omega = F::root_of_unity();
+        for _ in log_size..F::S {
+            omega = omega.square();
+        }
+        let coset = F::multiplicative_generator();
+
+        Self {
+            size,
+            log_size,
+            omega,
+            omega_inv: omega.invert().unwrap(),
+            size_inv: F::from(size as u64).invert().unwrap(),
+            coset,
+            coset_inv: coset.invert().unwrap(),
+        }
+    }
+
+    /// Value of the vanishing polynomial `X^size - 1` at `x`
+    pub fn vanishing_at(&self, x: F) -> F {
+        x.pow_vartime([self.size as u64]) - F::one()
+    }
+
+    /// Evaluations of the Lagrange basis of the domain at `x`, which should not be in the domain
+    pub fn lagrange_at(&self, x: F) -> Vec<F> {
+        // L_i(x) = omega^i * (x^size - 1) / (size * (x - omega^i))
+        let mut elements = Vec::with_capacity(self.size);
+        let mut element = F::one();
+        for _ in 0..self.size {
+            elements.push(element);
+            element *= self.omega;
+        }
+
+        let mut denominators: Vec<F> = elements.iter().map(|element| x - element).collect();
+        batch_invert(&mut denominators);
+
+        let scale = self.vanishing_at(x) * self.size_inv;
+        elements
+            .iter()
+            .zip(denominators)
+            .map(|(element, denominator)| scale * element * denominator)
+            .collect()
+    }
+
+    /// Turns the coefficients of a polynomial into its evaluations over the domain
+    pub fn fft(&self, values: &mut Vec<F>) {
+        values.resize(self.size, F::zero());
+        fft(values, self.omega, self.log_size);
+    }
+
+    /// Turns the evaluations of a polynomial over the domain into its coefficients
+    pub fn ifft(&self, values: &mut Vec<F>) {
+        values.resize(self.size, F::zero());
+        fft(values, self.omega_inv, self.log_size);
+        values
+            .par_iter_mut()
+            .for_each(|value| *value *= self.size_inv);
+    }
+
+    /// Turns the coefficients of a polynomial into its evaluations over the coset
+    pub fn coset_fft(&self, values: &mut Vec<F>) {
+        distribute_powers(values, self.coset);
+        self.fft(values);
+    }
+
+    /// Turns the evaluations of a polynomial over the coset into its coefficients
+    pub fn coset_ifft(&self, values: &mut Vec<F>) {
+        self.ifft(values);
+        distribute_powers(values, self.coset_inv);
+    }
+
+    /// Value of the vanishing polynomial over the coset, where it is constant
+    pub fn vanishing_on_coset(&self) -> F {
+        self.vanishing_at(self.coset)
+    }
+}
+
+/// Multiplies the `i`-th value by `g^i`
+fn distribute_powers<F: PrimeField>(values: &mut [F], g: F) {
+    let mut power = F::one();
+    for value in values.iter_mut() {
+        *value *= power;
+        power *= g;
+    }
+}
+
+/// Inverts every value in place with a single field inversion, none of them may be zero
+pub fn batch_invert<F: PrimeField>(values: &mut [F]) {
+    let mut products = Vec::with_capacity(values.len());
+    let mut product = F::one();
+    for value in values.iter() {
+        products.push(product);
+        product *= value;
+    }
+
+    let mut inverse = product.invert().unwrap();
+    for (value, product) in values.iter_mut().zip(products).rev() {
+        let value_inverse = inverse * product;
+        inverse *= *value;
+        *value = value_inverse;
+    }
+}
+
+fn bitreverse(mut n: usize, bits: u32) -> usize {
+    let mut reversed = 0;
+    for _ in 0..bits {
+        reversed = (reversed << 1) | (n & 1);
+        n >>= 1;
+    }
+    reversed
+}
+
+/// In place iterative Cooley-Tukey FFT of `values` (of length `2^log_n`) with root of unity
+/// `omega`. Butterflies of a layer are independent, so every layer is spread over rayon's threads.
+fn fft<F: PrimeField>(values: &mut [F], omega: F, log_n: u32) {
+    let n = values.len();
+    for i in 0..n {
+        let reversed = bitreverse(i, log_n);
+        if i < reversed {
+            values.swap(i, reversed);
+        }
+    }
+
+    let mut half = 1;
+    for _ in 0..log_n {
+        let w = omega.pow_vartime([(n / (2 * half)) as u64]);
+        let mut twiddles = Vec::with_capacity(half);
+        let mut twiddle = F::one();
+        for _ in 0..half {
+            twiddles.push(twiddle);
+            twiddle *= w;
+        }
+
+        values.par_chunks_mut(2 * half).for_each(|chunk| {
+            let (low, high) = chunk.split_at_mut(half);
+            for ((low, high), twiddle) in low.iter_mut().zip(high.iter_mut()).zip(twiddles.iter()) {
+                let t = *high * twiddle;
+                *high = *low - t;
+                *low += t;
+            }
+        });
+        half *= 2;
+    }
+}
@@ -0,0 +1,320 @@
+//! Groth16 over BN254, the proving system snarkjs runs on the circom circuits, natively in Rust.
+//!
+//! Follows the construction of the Groth16 paper as implemented by bellman and snarkjs: the R1CS
+//! is turned into a QAP over a radix-2 domain, with one extra `x * 0 = 0` constraint per public
+//! wire (and the constant one) so that public inputs can't be forged. The domain therefore has
+//! `num_constraints + num_public + 1` points, rounded up to a power of two, as with snarkjs.
+
+use ff::Field;
+use group::{
+    prime::{PrimeCurve, PrimeCurveAffine},
+    Curve, Group, GroupEncoding,
+};
+use pairing::{
+    arithmetic::{Engine, MillerLoopResult, MultiMillerLoop},
+    bn256::{Bn256, Fr, G1Affine, G2Affine, G1, G2},
+};
+use rand_core::RngCore;
+use rayon::prelude::*;
+
+use crate::r1cs::{Constraint, LinearCombination, R1cs};
+
+pub mod circuits;
+pub mod domain;
+pub mod multiexp;
+
+use domain::EvaluationDomain;
+use multiexp::multiexp;
+
+type G2Prepared = <Bn256 as MultiMillerLoop>::G2Prepared;
+type Gt = <Bn256 as Engine>::Gt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The number of public inputs doesn't match the verifying key
+    PublicInputCount { expected: usize, actual: usize },
+    /// The pairing check failed
+    InvalidProof,
+}
+
+#[derive(Clone, Debug)]
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    /// `(beta * u_i(tau) + alpha * v_i(tau) + w_i(tau)) / gamma` for the constant one and every
+    /// public wire
+    pub ic: Vec<G1Affine>,
+}
+
+impl VerifyingKey {
+    /// The points of the key in compressed form, in the order of its fields
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = [
+            self.alpha_g1.to_bytes().as_ref(),
+            self.beta_g2.to_bytes().as_ref(),
+            self.gamma_g2.to_bytes().as_ref(),
+            self.delta_g2.to_bytes().as_ref(),
+        ]
+        .concat();
+        for point in self.ic.iter() {
+            bytes.extend_from_slice(point.to_bytes().as_ref());
+        }
+        bytes
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProvingKey {
+    pub vk: VerifyingKey,
+    pub beta_g1: G1Affine,
+    pub delta_g1: G1Affine,
+    /// `u_i(tau)` for every wire
+    pub a_query: Vec<G1Affine>,
+    /// `v_i(tau)` for every wire, in both groups
+    pub b_g1_query: Vec<G1Affine>,
+    pub b_g2_query: Vec<G2Affine>,
+    /// `tau^i * z(tau) / delta` for every coefficient of the quotient polynomial
+    pub h_query: Vec<G1Affine>,
+    /// `(beta * u_i(tau) + alpha * v_i(tau) + w_i(tau)) / delta` for every private wire
+    pub l_query: Vec<G1Affine>,
+}
+
+impl ProvingKey {
+    /// The verifying key followed by the points of the key in compressed form, in the order of its
+    /// fields
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.vk.to_bytes();
+        bytes.extend_from_slice(self.beta_g1.to_bytes().as_ref());
+        bytes.extend_from_slice(self.delta_g1.to_bytes().as_ref());
+        for point in self.a_query.iter().chain(self.b_g1_query.iter()) {
+            bytes.extend_from_slice(point.to_bytes().as_ref());
+        }
+        for point in self.b_g2_query.iter() {
+            bytes.extend_from_slice(point.to_bytes().as_ref());
+        }
+        for point in self.h_query.iter().chain(self.l_query.iter()) {
+            bytes.extend_from_slice(point.to_bytes().as_ref());
+        }
+        bytes
+    }
+}
+
+/// Verifying key with the pairing of `alpha` and `beta` computed and the G2 points prepared for
+/// Miller loops
+#[derive(Clone)]
+pub struct PreparedVerifyingKey {
+    alpha_g1_beta_g2: Gt,
+    neg_gamma_g2: G2Prepared,
+    neg_delta_g2: G2Prepared,
+    ic: Vec<G1Affine>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl Proof {
+    /// The three points of the proof in compressed form, 128 bytes in total
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            self.a.to_bytes().as_ref(),
+            self.b.to_bytes().as_ref(),
+            self.c.to_bytes().as_ref(),
+        ]
+        .concat()
+    }
+}
+
+/// Number of points of the evaluation domain of `r1cs`
+pub fn domain_size(r1cs: &R1cs<Fr>) -> usize {
+    evaluation_domain(r1cs).size
+}
+
+fn evaluation_domain(r1cs: &R1cs<Fr>) -> EvaluationDomain<Fr> {
+    EvaluationDomain::new(r1cs.num_constraints() + r1cs.num_public + 1)
+}
+
+/// Multiplies `generator` by every scalar, returning the products in affine form
+fn batch_mul<G>(generator: G, scalars: &[Fr]) -> Vec<G::Affine>
+where
+    G: PrimeCurve + Group<Scalar = Fr>,
+{
+    let products: Vec<G> = scalars
+        .par_iter()
+        .map(|scalar| generator * scalar)
+        .collect();
+    let mut affine = vec![G::Affine::identity(); products.len()];
+    G::batch_normalize(&products, &mut affine);
+    affine
+}
+
+/// Circuit specific setup of `r1cs`, using `rng` for the toxic waste
+pub fn setup<R: RngCore>(r1cs: &R1cs<Fr>, mut rng: R) -> ProvingKey {
+    let domain = evaluation_domain(r1cs);
+    let tau = loop {
+        let tau = Fr::random(&mut rng);
+        if domain.vanishing_at(tau) != Fr::zero() {
+            break tau;
+        }
+    };
+    let alpha = Fr::random(&mut rng);
+    let beta = Fr::random(&mut rng);
+    let gamma = Fr::random(&mut rng);
+    let gamma_inv = gamma.invert().unwrap();
+    let delta = Fr::random(&mut rng);
+    let delta_inv = delta.invert().unwrap();
+
+    // Evaluate the QAP polynomials of every wire at tau
+    let lagrange = domain.lagrange_at(tau);
+    let mut u = vec![Fr::zero(); r1cs.num_wires];
+    let mut v = vec![Fr::zero(); r1cs.num_wires];
+    let mut w = vec![Fr::zero(); r1cs.num_wires];
+    for (constraint, l) in r1cs.constraints.iter().zip(lagrange.iter()) {
+        for (wire, coefficient) in constraint.a.terms.iter() {
+            u[*wire] += *coefficient * l;
+        }
+        for (wire, coefficient) in constraint.b.terms.iter() {
+            v[*wire] += *coefficient * l;
+        }
+        for (wire, coefficient) in constraint.c.terms.iter() {
+            w[*wire] += *coefficient * l;
+        }
+    }
+    let public_rows = &lagrange[r1cs.num_constraints()..=r1cs.num_constraints() + r1cs.num_public];
+    for (u, l) in u.iter_mut().zip(public_rows) {
+        *u += l;
+    }
+
+    let combined: Vec<Fr> = (0..r1cs.num_wires)
+        .map(|i| beta * u[i] + alpha * v[i] + w[i])
+        .collect();
+    let (ic, l) = combined.split_at(r1cs.num_public + 1);
+    let ic: Vec<Fr> = ic.iter().map(|value| *value * gamma_inv).collect();
+    let l: Vec<Fr> = l.iter().map(|value| *value * delta_inv).collect();
+
+    let z_delta_inv = domain.vanishing_at(tau) * delta_inv;
+    let mut h = Vec::with_capacity(domain.size - 1);
+    let mut power = z_delta_inv;
+    for _ in 0..domain.size - 1 {
+        h.push(power);
+        power *= tau;
+    }
+
+    let g1 = G1::generator();
+    let g2 = G2::generator();
+    ProvingKey {
+        vk: VerifyingKey {
+            alpha_g1: (g1 * alpha).to_affine(),
+            beta_g2: (g2 * beta).to_affine(),
+            gamma_g2: (g2 * gamma).to_affine(),
+            delta_g2: (g2 * delta).to_affine(),
+            ic: batch_mul(g1, &ic),
+        },
+        beta_g1: (g1 * beta).to_affine(),
+        delta_g1: (g1 * delta).to_affine(),
+        a_query: batch_mul(g1, &u),
+        b_g1_query: batch_mul(g1, &v),
+        b_g2_query: batch_mul(g2, &v),
+        h_query: batch_mul(g1, &h),
+        l_query: batch_mul(g1, &l),
+    }
+}
+
+/// Coefficients of the quotient `h = (A * B - C) / z` of the QAP of `r1cs` under `witness`
+fn quotient(domain: &EvaluationDomain<Fr>, r1cs: &R1cs<Fr>, witness: &[Fr]) -> Vec<Fr> {
+    let evaluate = |select: fn(&Constraint<Fr>) -> &LinearCombination<Fr>| -> Vec<Fr> {
+        r1cs.constraints
+            .par_iter()
+            .map(|constraint| select(constraint).evaluate(witness))
+            .collect()
+    };
+    let mut a = evaluate(|constraint| &constraint.a);
+    let mut b = evaluate(|constraint| &constraint.b);
+    let mut c = evaluate(|constraint| &constraint.c);
+    // The public wires' `x * 0 = 0` constraints
+    a.extend_from_slice(&witness[..=r1cs.num_public]);
+
+    for values in [&mut a, &mut b, &mut c] {
+        domain.ifft(values);
+        domain.coset_fft(values);
+    }
+
+    // A * B - C vanishes over the domain, so can be divided by z over the coset
+    let z_inv = domain.vanishing_on_coset().invert().unwrap();
+    let mut h: Vec<Fr> = a
+        .par_iter()
+        .zip(b.par_iter())
+        .zip(c.par_iter())
+        .map(|((a, b), c)| (*a * b - c) * z_inv)
+        .collect();
+    domain.coset_ifft(&mut h);
+    h.truncate(domain.size - 1);
+    h
+}
+
+/// Proves that `witness` satisfies `r1cs`, with `rng` providing the blinding factors
+pub fn prove<R: RngCore>(pk: &ProvingKey, r1cs: &R1cs<Fr>, witness: &[Fr], mut rng: R) -> Proof {
+    assert_eq!(witness.len(), r1cs.num_wires);
+    let domain = evaluation_domain(r1cs);
+    let h = quotient(&domain, r1cs, witness);
+
+    let r = Fr::random(&mut rng);
+    let s = Fr::random(&mut rng);
+
+    let a = multiexp::<G1>(witness, &pk.a_query) + pk.vk.alpha_g1 + pk.delta_g1 * r;
+    let b_g2 = multiexp::<G2>(witness, &pk.b_g2_query) + pk.vk.beta_g2 + pk.vk.delta_g2 * s;
+    let b_g1 = multiexp::<G1>(witness, &pk.b_g1_query) + pk.beta_g1 + pk.delta_g1 * s;
+    let c = multiexp::<G1>(&witness[r1cs.num_public + 1..], &pk.l_query)
+        + multiexp::<G1>(&h, &pk.h_query)
+        + a * s
+        + b_g1 * r
+        - pk.delta_g1 * (r * s);
+
+    Proof {
+        a: a.to_affine(),
+        b: b_g2.to_affine(),
+        c: c.to_affine(),
+    }
+}
+
+pub fn prepare_verifying_key(vk: &VerifyingKey) -> PreparedVerifyingKey {
+    PreparedVerifyingKey {
+        alpha_g1_beta_g2: Bn256::pairing(&vk.alpha_g1, &vk.beta_g2),
+        neg_gamma_g2: (-vk.gamma_g2).into(),
+        neg_delta_g2: (-vk.delta_g2).into(),
+        ic: vk.ic.clone(),
+    }
+}
+
+/// Verifies `proof` against the values of the public wires
+pub fn verify(
+    pvk: &PreparedVerifyingKey,
+    public_inputs: &[Fr],
+    proof: &Proof,
+) -> Result<(), Error> {
+    if public_inputs.len() + 1 != pvk.ic.len() {
+        return Err(Error::PublicInputCount {
+            expected: pvk.ic.len() - 1,
+            actual: public_inputs.len(),
+        });
+    }
+    let inputs = (multiexp::<G1>(public_inputs, &pvk.ic[1..]) + pvk.ic[0]).to_affine();
+
+    // e(A, B) = e(alpha, beta) * e(inputs, gamma) * e(C, delta)
+    let b: G2Prepared = proof.b.into();
+    let miller_loop = Bn256::multi_miller_loop(&[
+        (&proof.a, &b),
+        (&inputs, &pvk.neg_gamma_g2),
+        (&proof.c, &pvk.neg_delta_g2),
+    ]);
+    if miller_loop.final_exponentiation() == pvk.alpha_g1_beta_g2 {
+        Ok(())
+    } else {
+        Err(Error::InvalidProof)
+    }
+}
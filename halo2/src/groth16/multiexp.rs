@@ -0,0 +1,72 @@
+//! Multi-scalar multiplication, the bulk of the work of the Groth16 prover.
+
+use ff::PrimeField;
+use group::Curve;
+use rayon::prelude::*;
+
+/// Computes `sum(scalars[i] * bases[i])`, splitting the bases between rayon's threads.
+///
+/// Scalars are read through their little endian representation, as used by BN254's `Fr`.
+pub fn multiexp<G>(scalars: &[G::Scalar], bases: &[G::AffineRepr]) -> G
+where
+    G: Curve,
+    G::AffineRepr: Sync,
+{
+    assert_eq!(scalars.len(), bases.len());
+    let chunk = (bases.len() / rayon::current_num_threads()).max(1);
+    scalars
+        .par_chunks(chunk)
+        .zip(bases.par_chunks(chunk))
+        .map(|(scalars, bases)| multiexp_serial::<G>(scalars, bases))
+        .reduce(G::identity, |a, b| a + b)
+}
+
+/// Pippenger's bucket method: the scalars are cut into windows of `c` bits, and for every window
+/// the bases are summed into one bucket per window value before the buckets are combined
+fn multiexp_serial<G: Curve>(scalars: &[G::Scalar], bases: &[G::AffineRepr]) -> G {
+    let scalars: Vec<_> = scalars.iter().map(|scalar| scalar.to_repr()).collect();
+    let c = if bases.len() < 4 {
+        1
+    } else if bases.len() < 32 {
+        3
+    } else {
+        (bases.len() as f64).ln().ceil() as usize
+    };
+    let windows = G::Scalar::NUM_BITS as usize / c + 1;
+
+    let mut acc = G::identity();
+    for window in (0..windows).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        let mut buckets = vec![G::identity(); (1 << c) - 1];
+        for (scalar, base) in scalars.iter().zip(bases) {
+            let value = window_value(scalar.as_ref(), window * c, c);
+            if value != 0 {
+                buckets[value - 1] += base;
+            }
+        }
+
+        // sum(i * buckets[i - 1]) as a running sum of the buckets from the top
+        let mut running_sum = G::identity();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            acc += running_sum;
+        }
+    }
+    acc
+}
+
+/// The `c` bits of the little endian `bytes` starting at bit `skip`
+fn window_value(bytes: &[u8], skip: usize, c: usize) -> usize {
+    let skip_bytes = skip / 8;
+    if skip_bytes >= bytes.len() {
+        return 0;
+    }
+    let mut word = [0u8; 8];
+    for (word, byte) in word.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *word = *byte;
+    }
+    ((u64::from_le_bytes(word) >> (skip - skip_bytes * 8)) % (1 << c)) as usize
+}
@@ -0,0 +1,78 @@
+//! Thin wrappers around the IPA key generation, proving and verification functions of
+//! `halo2_proofs`, the transparent alternative to KZG that needs no trusted setup.
+//!
+//! Commitments are points of Vesta (`EqAffine`), so circuits are defined over its scalar field,
+//! Pallas' base field `Fp`. The parameters are generated from a hash to the curve, with no secret,
+//! and are therefore the same for every run.
+
+use halo2_proofs::{
+    halo2curves::pasta::{EqAffine, Fp},
+    plonk::{
+        create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, Error, ProvingKey, VerifyingKey,
+    },
+    poly::{
+        commitment::ParamsProver,
+        ipa::{
+            commitment::{IPACommitmentScheme, ParamsIPA},
+            multiopen::{ProverIPA, VerifierIPA},
+            strategy::SingleStrategy,
+        },
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+};
+use rand_core::OsRng;
+
+/// Parameters for circuits of `2^k` rows
+pub fn params(k: u32) -> ParamsIPA<EqAffine> {
+    ParamsIPA::new(k)
+}
+
+/// Generates the verifying key, and from it the proving key, of `circuit`
+pub fn keygen<C: Circuit<Fp>>(
+    params: &ParamsIPA<EqAffine>,
+    circuit: &C,
+) -> Result<ProvingKey<EqAffine>, Error> {
+    let vk = keygen_vk(params, circuit)?;
+    keygen_pk(params, vk, circuit)
+}
+
+/// Proves `circuits` with a Blake2b transcript, returning the proof.
+///
+/// `instances` holds, for every circuit, the values of each of its instance columns.
+pub fn prove<C: Circuit<Fp>>(
+    params: &ParamsIPA<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuits: &[C],
+    instances: &[&[&[Fp]]],
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof::<IPACommitmentScheme<EqAffine>, ProverIPA<EqAffine>, _, _, _, _>(
+        params,
+        pk,
+        circuits,
+        instances,
+        OsRng,
+        &mut transcript,
+    )?;
+    Ok(transcript.finalize())
+}
+
+/// Verifies a proof created by [`prove`]
+pub fn verify(
+    params: &ParamsIPA<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    instances: &[&[&[Fp]]],
+    proof: &[u8],
+) -> Result<(), Error> {
+    let strategy = SingleStrategy::new(params);
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(proof);
+    verify_proof::<_, VerifierIPA<EqAffine>, _, _, _>(
+        params,
+        vk,
+        strategy,
+        instances,
+        &mut transcript,
+    )
+}
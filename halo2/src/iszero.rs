@@ -0,0 +1,325 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Cell, Layouter, SimpleFloorPlanner, Value},
+    plonk::{Assigned, Circuit, Column, ConstraintSystem, Error, FloorPlanner, Instance},
+};
+use std::{marker::PhantomData, ops::Neg};
+
+use crate::fit::max_repeats;
+use crate::standard_plonk::{PlonkConfig, StandardCs, StandardPlonk};
+
+/// Circuit repeating `iszero(a)` using only the gates of a standard PLONK constraint system.
+///
+/// One iteration satisfies the two IsZero constraints `1 - (inv * in) = out` and `in * out = 0`
+/// with three gates (see `benches/iszeroREADME.md`), mirroring circom's `IsZero` template. It is
+/// laid out by the floor planner `P`.
+#[derive(Debug)]
+pub struct IsZeroCircuit<F: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub a: Value<F>,
+    pub repeats: usize,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, P: FloorPlanner> IsZeroCircuit<F, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> IsZeroCircuit<F, Q> {
+        IsZeroCircuit {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Clone for IsZeroCircuit<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> IsZeroCircuit<F> {
+    /// Creates a circuit checking `iszero(a)` `repeats` times
+    pub fn new(a: Value<F>, repeats: usize) -> Self {
+        Self {
+            a,
+            repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Largest number of repeats that fits in a table of `2^k` rows
+    pub fn repeats_for_k(k: u32) -> usize {
+        max_repeats(k, |repeats| {
+            (Self::new(Value::known(F::one()), repeats), vec![])
+        })
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for IsZeroCircuit<F, P> {
+    type Config = PlonkConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> PlonkConfig {
+        StandardPlonk::configure(meta)
+    }
+
+    fn synthesize(&self, config: PlonkConfig, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        let cs = StandardPlonk::new(config);
+
+        for _ in 0..self.repeats {
+            assign_iszero(&cs, &mut layouter, self.a)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// [`IsZeroCircuit`] on a `StandardPlonk` of `WIDTH` lanes, which lays `WIDTH` consecutive gates
+/// on each row: a repeat takes `3 / WIDTH` rows, for `WIDTH` times as many columns. It is laid out
+/// by the floor planner `P`.
+#[derive(Debug)]
+pub struct IsZeroWideCircuit<F: FieldExt, const WIDTH: usize, P: FloorPlanner = SimpleFloorPlanner>
+{
+    pub a: Value<F>,
+    pub repeats: usize,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, const WIDTH: usize, P: FloorPlanner> IsZeroWideCircuit<F, WIDTH, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> IsZeroWideCircuit<F, WIDTH, Q> {
+        IsZeroWideCircuit {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, const WIDTH: usize, P: FloorPlanner> Clone for IsZeroWideCircuit<F, WIDTH, P> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, const WIDTH: usize> IsZeroWideCircuit<F, WIDTH> {
+    /// Creates a circuit checking `iszero(a)` `repeats` times
+    pub fn new(a: Value<F>, repeats: usize) -> Self {
+        Self {
+            a,
+            repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Largest number of repeats that fits in a table of `2^k` rows
+    pub fn repeats_for_k(k: u32) -> usize {
+        max_repeats(k, |repeats| {
+            (Self::new(Value::known(F::one()), repeats), vec![])
+        })
+    }
+}
+
+impl<F: FieldExt, const WIDTH: usize, P: FloorPlanner> Circuit<F>
+    for IsZeroWideCircuit<F, WIDTH, P>
+{
+    type Config = Vec<PlonkConfig>;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Vec<PlonkConfig> {
+        StandardPlonk::configure_lanes(meta, WIDTH)
+    }
+
+    fn synthesize(
+        &self,
+        config: Vec<PlonkConfig>,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = StandardPlonk::with_lanes(config);
+
+        for _ in 0..self.repeats {
+            assign_iszero(&cs, &mut layouter, self.a)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Assigns the three gates and copy constraints of one `iszero(a)`, returning the cells holding
+/// `in` and `out`
+fn assign_iszero<F: FieldExt>(
+    cs: &StandardPlonk<F>,
+    layouter: &mut impl Layouter<F>,
+    a: Value<F>,
+) -> Result<(Cell, Cell), Error> {
+    let a: Value<Assigned<_>> = a.into();
+    // such that a * inv_neg = -1
+    let inv_neg: Value<Assigned<_>> = a.invert().neg();
+    let one = Assigned::from(F::one());
+    let zero = Assigned::Zero;
+
+    // first gate, the mul gate
+    let (_a1, b1, c1) = cs.raw_multiply(layouter, || {
+        a.zip(inv_neg).map(|(a, inv_neg)| (inv_neg, a, inv_neg * a))
+    })?;
+
+    // addition gate, where we are going to create out
+    let (a2, _b2, c2) = cs.raw_add(layouter, || {
+        a.zip(inv_neg)
+            .map(|(a, inv_neg)| (inv_neg * a, one, one + (inv_neg * a)))
+    })?;
+
+    // final gate, the second multiplication gate
+    let (a3, b3, _c3) = cs.raw_multiply(layouter, || {
+        a.zip(inv_neg)
+            .map(|(a, inv_neg)| (one + (inv_neg * a), a, zero))
+    })?;
+
+    // copy constraints
+    cs.copy(layouter, c1, a2)?;
+    cs.copy(layouter, c2, a3)?;
+    cs.copy(layouter, b1, b3)?;
+
+    Ok((b1, c2))
+}
+
+/// Columns of [`IsZeroPublicCircuit`]: those of the standard PLONK gate and an instance column
+#[derive(Clone, Debug)]
+pub struct IsZeroPublicConfig {
+    pub plonk: PlonkConfig,
+    pub instance: Column<Instance>,
+}
+
+/// [`IsZeroCircuit`] with the interface of circom's `IsZero_Generic`: every `out` is exposed in
+/// an instance column, as `signal output out[k]` makes it a public input of the Groth16 proof.
+///
+/// The instance column holds the `repeats` outputs, followed by `a` when `expose_input` is set,
+/// the order snarkjs lists public signals in. Exposing `a` also binds the input of every repeat
+/// to the same value, like the single `in` signal of the circom template. It is laid out by the
+/// floor planner `P`.
+#[derive(Debug)]
+pub struct IsZeroPublicCircuit<F: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub a: Value<F>,
+    pub repeats: usize,
+    pub expose_input: bool,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, P: FloorPlanner> IsZeroPublicCircuit<F, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> IsZeroPublicCircuit<F, Q> {
+        IsZeroPublicCircuit {
+            a: self.a,
+            repeats: self.repeats,
+            expose_input: self.expose_input,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Clone for IsZeroPublicCircuit<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            repeats: self.repeats,
+            expose_input: self.expose_input,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> IsZeroPublicCircuit<F> {
+    /// Creates a circuit checking `iszero(a)` `repeats` times and exposing the results, and `a`
+    /// itself if `expose_input` is set
+    pub fn new(a: Value<F>, repeats: usize, expose_input: bool) -> Self {
+        Self {
+            a,
+            repeats,
+            expose_input,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Values of the instance column of the circuit checking `iszero(a)` `repeats` times
+    pub fn instances(a: F, repeats: usize, expose_input: bool) -> Vec<Vec<F>> {
+        let out = if a == F::zero() { F::one() } else { F::zero() };
+        let mut column = vec![out; repeats];
+        if expose_input {
+            column.push(a);
+        }
+        vec![column]
+    }
+
+    /// Largest number of repeats that fits in a table of `2^k` rows
+    pub fn repeats_for_k(k: u32, expose_input: bool) -> usize {
+        max_repeats(k, |repeats| {
+            (
+                Self::new(Value::known(F::one()), repeats, expose_input),
+                Self::instances(F::one(), repeats, expose_input),
+            )
+        })
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for IsZeroPublicCircuit<F, P> {
+    type Config = IsZeroPublicConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            repeats: self.repeats,
+            expose_input: self.expose_input,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> IsZeroPublicConfig {
+        let plonk = StandardPlonk::configure(meta);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        IsZeroPublicConfig { plonk, instance }
+    }
+
+    fn synthesize(
+        &self,
+        config: IsZeroPublicConfig,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = StandardPlonk::new(config.plonk);
+
+        for row in 0..self.repeats {
+            let (input, out) = assign_iszero(&cs, &mut layouter, self.a)?;
+            layouter.constrain_instance(out, config.instance, row)?;
+            if self.expose_input {
+                layouter.constrain_instance(input, config.instance, self.repeats)?;
+            }
+        }
+
+        Ok(())
+    }
+}
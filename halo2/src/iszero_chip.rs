@@ -0,0 +1,215 @@
+//! IsZero as a dedicated chip, the way it is usually written in Halo2 (e.g. the `is_zero` gadget of
+//! zkevm-circuits), rather than ported gate by gate from R1CS as in [`crate::iszero`].
+//!
+//! A single gate checks both IsZero constraints, `out = 1 - value * value_inv` and
+//! `value * out = 0`, on one row holding `value`, its inverse and `out`. Both are of degree 2 in the
+//! advice columns (3 with the selector, as the standard PLONK gate), so one check takes one row
+//! instead of the three gates and three copy constraints of [`crate::iszero::IsZeroCircuit`].
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Chip, Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Expression, FloorPlanner, Selector},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::fit::max_repeats;
+
+/// Columns of the IsZero gate
+#[derive(Clone, Debug)]
+pub struct IsZeroConfig {
+    pub value: Column<Advice>,
+    pub value_inv: Column<Advice>,
+    pub out: Column<Advice>,
+
+    pub q_enable: Selector,
+}
+
+/// Instructions of a chip checking whether values are zero
+pub trait IsZeroInstructions<F: FieldExt>: Chip<F> {
+    /// Variable holding a value in the circuit
+    type Num;
+
+    /// Assigns `value` and returns the cells holding it and `out`, 1 if it is zero and 0 otherwise
+    fn is_zero(
+        &self,
+        layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<(Self::Num, Self::Num), Error>;
+}
+
+/// Chip implementing [`IsZeroInstructions`] with one region (and so one row) per check
+pub struct IsZeroChip<F: FieldExt> {
+    config: IsZeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IsZeroChip<F> {
+    pub fn new(config: IsZeroConfig) -> Self {
+        IsZeroChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allocates the three advice columns and the selector and creates the "is zero" gate
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> IsZeroConfig {
+        let value = meta.advice_column();
+        let value_inv = meta.advice_column();
+        let out = meta.advice_column();
+
+        // `value` and `out` can be copied from and to other chips
+        meta.enable_equality(value);
+        meta.enable_equality(out);
+
+        let q_enable = meta.selector();
+
+        meta.create_gate("is zero", |meta| {
+            let q_enable = meta.query_selector(q_enable);
+            let value = meta.query_advice(value, Rotation::cur());
+            let value_inv = meta.query_advice(value_inv, Rotation::cur());
+            let out = meta.query_advice(out, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // out = 1 - value * value_inv
+                q_enable.clone() * (out.clone() - one + value.clone() * value_inv),
+                // value * out = 0
+                q_enable * value * out,
+            ]
+        });
+
+        IsZeroConfig {
+            value,
+            value_inv,
+            out,
+            q_enable,
+        }
+    }
+}
+
+impl<F: FieldExt> Chip<F> for IsZeroChip<F> {
+    type Config = IsZeroConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> IsZeroInstructions<F> for IsZeroChip<F> {
+    type Num = AssignedCell<F, F>;
+
+    fn is_zero(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<(Self::Num, Self::Num), Error> {
+        layouter.assign_region(
+            || "is zero",
+            |mut region| {
+                self.config.q_enable.enable(&mut region, 0)?;
+
+                let value_cell =
+                    region.assign_advice(|| "value", self.config.value, 0, || value)?;
+                // Zero has no inverse, any value satisfies the gate then
+                let value_inv = value.map(|value| value.invert().unwrap_or(F::zero()));
+                region.assign_advice(|| "value inv", self.config.value_inv, 0, || value_inv)?;
+                let out = value.map(|value| {
+                    if value == F::zero() {
+                        F::one()
+                    } else {
+                        F::zero()
+                    }
+                });
+                let out = region.assign_advice(|| "out", self.config.out, 0, || out)?;
+
+                Ok((value_cell, out))
+            },
+        )
+    }
+}
+
+/// Circuit repeating `iszero(a)` with [`IsZeroChip`], one row per repeat, laid out by the floor
+/// planner `P`
+#[derive(Debug)]
+pub struct IsZeroChipCircuit<F: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub a: Value<F>,
+    pub repeats: usize,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, P: FloorPlanner> IsZeroChipCircuit<F, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> IsZeroChipCircuit<F, Q> {
+        IsZeroChipCircuit {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Clone for IsZeroChipCircuit<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> IsZeroChipCircuit<F> {
+    /// Creates a circuit checking `iszero(a)` `repeats` times
+    pub fn new(a: Value<F>, repeats: usize) -> Self {
+        Self {
+            a,
+            repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Largest number of repeats that fits in a table of `2^k` rows
+    pub fn repeats_for_k(k: u32) -> usize {
+        max_repeats(k, |repeats| {
+            (Self::new(Value::known(F::one()), repeats), vec![])
+        })
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for IsZeroChipCircuit<F, P> {
+    type Config = IsZeroConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> IsZeroConfig {
+        IsZeroChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: IsZeroConfig,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = IsZeroChip::new(config);
+
+        for _ in 0..self.repeats {
+            chip.is_zero(layouter.namespace(|| "iszero"), self.a)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Layout of circuits: laying any circuit out with another floor planner, and counting the rows it
+//! takes.
+//!
+//! Every circuit of this crate takes its floor planner as a type parameter defaulting to
+//! `SimpleFloorPlanner`, which places each region below the last one using any of its columns, and
+//! can be switched to another one, such as `floor_planner::V1`, which packs regions into the gaps
+//! left by others, with its `with_floor_planner` method. The circuits of `zkevm-circuits` pick
+//! `SimpleFloorPlanner` themselves, so [`WithFloorPlanner`] runs their configuration and synthesis
+//! under another planner. The benches can then tell how much of a circuit's size (and so of its
+//! proving time) is down to its layout.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{
+        Advice, Any, Assigned, Assignment, Challenge, Circuit, Column, ConstraintSystem, Error,
+        Fixed, FloorPlanner, Instance, Selector,
+    },
+};
+use std::marker::PhantomData;
+
+/// `C` laid out by the floor planner `P` instead of its own, for circuits without a floor planner
+/// parameter such as those of `zkevm-circuits`
+#[derive(Debug)]
+pub struct WithFloorPlanner<C, P> {
+    pub circuit: C,
+    _planner: PhantomData<P>,
+}
+
+impl<C, P> WithFloorPlanner<C, P> {
+    pub fn new(circuit: C) -> Self {
+        WithFloorPlanner {
+            circuit,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, C: Circuit<F>, P: FloorPlanner> Circuit<F> for WithFloorPlanner<C, P> {
+    type Config = C::Config;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.circuit.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> C::Config {
+        C::configure(meta)
+    }
+
+    fn synthesize(&self, config: C::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        self.circuit.synthesize(config, layouter)
+    }
+}
+
+/// Returns the number of rows `circuit` uses when laid out by its floor planner: one more than
+/// the last row it enables a selector on or assigns a cell of, including the constants the
+/// planner assigns after the regions.
+///
+/// The rows available to a circuit of `2^k` rows are given by [`crate::fit::usable_rows`].
+pub fn rows_used<F: FieldExt, C: Circuit<F>>(circuit: &C) -> usize {
+    let mut cs = ConstraintSystem::default();
+    let config = C::configure(&mut cs);
+    let mut counter = RowCounter::default();
+    C::FloorPlanner::synthesize(&mut counter, circuit, config, cs.constants().clone())
+        .expect("laying out the circuit should not fail");
+    counter.rows
+}
+
+/// Assignment keeping track of the last row used, without computing nor storing any value
+#[derive(Default)]
+struct RowCounter {
+    rows: usize,
+}
+
+impl RowCounter {
+    fn use_row(&mut self, row: usize) {
+        self.rows = self.rows.max(row + 1);
+    }
+}
+
+impl<F: FieldExt> Assignment<F> for RowCounter {
+    fn enter_region<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        _: Column<Any>,
+        left_row: usize,
+        _: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.use_row(left_row);
+        self.use_row(right_row);
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        // Fills the rest of the column, however many rows the table ends up with
+        Ok(())
+    }
+
+    fn get_challenge(&self, _: Challenge) -> Value<F> {
+        Value::unknown()
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
@@ -0,0 +1,308 @@
+//! circomlib's `LessThan(n)` as a chip, and the `RepeatedLessThan(k, n)` circuit of
+//! `groth16/range/range.circom` built on it, so that the range benches of both proving systems
+//! check the same thing.
+//!
+//! As in the circom template, `a < b` for `a` and `b` of at most `n` bits is read off
+//! `a + 2^n - b`: decomposed into `n + 1` bits by [`Num2BitsChip`], its top bit is set exactly when
+//! `a >= b`, and `out = 1 - top_bit`. One check takes a row computing `a + 2^n - b` and `out`,
+//! next to the `n + 1` rows of the decomposition.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Chip, Layouter, SimpleFloorPlanner, Value},
+    plonk::{
+        Advice, Circuit, Column, ConstraintSystem, Error, Expression, FloorPlanner, Instance,
+        Selector,
+    },
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::fit::max_repeats;
+use crate::num2bits::{bit, Num2BitsChip, Num2BitsConfig, Num2BitsInstructions};
+
+/// Columns of the "less than" gate, and those of the decomposition into bits it relies on
+#[derive(Clone, Debug)]
+pub struct LessThanConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    /// `a + 2^n - b`
+    pub shifted: Column<Advice>,
+    /// Bit `n` of `a + 2^n - b`
+    pub top_bit: Column<Advice>,
+    pub out: Column<Advice>,
+
+    pub q_enable: Selector,
+
+    pub num2bits: Num2BitsConfig,
+    /// Number of bits of the values compared
+    pub n: usize,
+}
+
+/// Instructions of a chip comparing values
+pub trait LessThanInstructions<F: FieldExt>: Chip<F> {
+    /// Variable holding a value in the circuit
+    type Num;
+
+    /// Assigns a private input
+    fn load_private(&self, layouter: impl Layouter<F>, value: Value<F>)
+        -> Result<Self::Num, Error>;
+
+    /// Returns a variable holding 1 if `a < b` and 0 otherwise, for `a` and `b` of at most `n`
+    /// bits
+    fn less_than(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &Self::Num,
+        b: &Self::Num,
+    ) -> Result<Self::Num, Error>;
+}
+
+/// Chip implementing [`LessThanInstructions`] for values of `n` bits, `n` being fixed when it is
+/// configured as it is a parameter of the circom template
+pub struct LessThanChip<F: FieldExt> {
+    config: LessThanConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LessThanChip<F> {
+    pub fn new(config: LessThanConfig) -> Self {
+        LessThanChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allocates the five advice columns and the selector of the "less than" gate, and configures
+    /// a [`Num2BitsChip`] on columns of its own
+    pub fn configure(meta: &mut ConstraintSystem<F>, n: usize) -> LessThanConfig {
+        // Same bound as circomlib, `a + 2^n - b` should not wrap around the field
+        assert!(n <= 252, "values compared should have at most 252 bits");
+
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let shifted = meta.advice_column();
+        let top_bit = meta.advice_column();
+        let out = meta.advice_column();
+
+        // The inputs are copied in, `a + 2^n - b` to its decomposition and its top bit from it
+        for column in [a, b, shifted, top_bit, out] {
+            meta.enable_equality(column);
+        }
+
+        let q_enable = meta.selector();
+        let offset = F::from(2).pow(&[n as u64, 0, 0, 0]);
+
+        meta.create_gate("less than", |meta| {
+            let q_enable = meta.query_selector(q_enable);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let shifted = meta.query_advice(shifted, Rotation::cur());
+            let top_bit = meta.query_advice(top_bit, Rotation::cur());
+            let out = meta.query_advice(out, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // shifted = a + 2^n - b
+                q_enable.clone() * (shifted - a - Expression::Constant(offset) + b),
+                // out = 1 - top_bit
+                q_enable * (out - one + top_bit),
+            ]
+        });
+
+        LessThanConfig {
+            a,
+            b,
+            shifted,
+            top_bit,
+            out,
+            q_enable,
+            num2bits: Num2BitsChip::configure(meta),
+            n,
+        }
+    }
+}
+
+impl<F: FieldExt> Chip<F> for LessThanChip<F> {
+    type Config = LessThanConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> LessThanInstructions<F> for LessThanChip<F> {
+    type Num = AssignedCell<F, F>;
+
+    fn load_private(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<Self::Num, Error> {
+        layouter.assign_region(
+            || "load private",
+            |mut region| region.assign_advice(|| "private input", self.config.a, 0, || value),
+        )
+    }
+
+    fn less_than(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &Self::Num,
+        b: &Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let n = self.config.n;
+        let offset = F::from(2).pow(&[n as u64, 0, 0, 0]);
+
+        let (shifted, top_bit, out) = layouter.assign_region(
+            || "less than",
+            |mut region| {
+                self.config.q_enable.enable(&mut region, 0)?;
+
+                a.copy_advice(|| "a", &mut region, self.config.a, 0)?;
+                b.copy_advice(|| "b", &mut region, self.config.b, 0)?;
+                let shifted = a.value().zip(b.value()).map(|(a, b)| *a + offset - *b);
+                let top_bit = shifted.map(|shifted| bit(&shifted, n));
+                let shifted =
+                    region.assign_advice(|| "shifted", self.config.shifted, 0, || shifted)?;
+                let top_bit =
+                    region.assign_advice(|| "top bit", self.config.top_bit, 0, || top_bit)?;
+                let out = top_bit.value().map(|top_bit| F::one() - top_bit);
+                let out = region.assign_advice(|| "out", self.config.out, 0, || out)?;
+
+                Ok((shifted, top_bit, out))
+            },
+        )?;
+
+        // The top bit is that of the decomposition of `a + 2^n - b` into `n + 1` bits
+        let num2bits = Num2BitsChip::new(self.config.num2bits.clone());
+        let bits = num2bits.num2bits(layouter.namespace(|| "num2bits"), &shifted, n + 1)?;
+        layouter.assign_region(
+            || "copy top bit",
+            |mut region| region.constrain_equal(bits[n].cell(), top_bit.cell()),
+        )?;
+
+        Ok(out)
+    }
+}
+
+/// Columns of [`RepeatedLessThanCircuit`]: those of [`LessThanChip`] and an instance column
+#[derive(Clone, Debug)]
+pub struct RepeatedLessThanConfig {
+    pub less_than: LessThanConfig,
+    pub instance: Column<Instance>,
+}
+
+/// `RepeatedLessThan(repeats, N)` of `range.circom`: `repeats` checks of `a < b` for the private
+/// `N` bit inputs `a` and `b`, the result of the first being exposed in an instance column as the
+/// public output of the circom circuit. It is laid out by the floor planner `P`.
+#[derive(Debug)]
+pub struct RepeatedLessThanCircuit<
+    F: FieldExt,
+    const N: usize,
+    P: FloorPlanner = SimpleFloorPlanner,
+> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+    pub repeats: usize,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, const N: usize, P: FloorPlanner> RepeatedLessThanCircuit<F, N, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> RepeatedLessThanCircuit<F, N, Q> {
+        RepeatedLessThanCircuit {
+            a: self.a,
+            b: self.b,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize, P: FloorPlanner> Clone for RepeatedLessThanCircuit<F, N, P> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            b: self.b,
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> RepeatedLessThanCircuit<F, N> {
+    /// Creates a circuit checking `a < b` `repeats` times
+    pub fn new(a: Value<F>, b: Value<F>, repeats: usize) -> Self {
+        Self {
+            a,
+            b,
+            repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Values of the instance column of the circuit checking `a < b`
+    pub fn instances(a: u64, b: u64) -> Vec<Vec<F>> {
+        vec![vec![F::from(u64::from(a < b))]]
+    }
+
+    /// Largest number of repeats that fits in a table of `2^k` rows
+    pub fn repeats_for_k(k: u32) -> usize {
+        max_repeats(k, |repeats| {
+            (
+                Self::new(Value::known(F::from(2)), Value::known(F::from(3)), repeats),
+                Self::instances(2, 3),
+            )
+        })
+    }
+}
+
+impl<F: FieldExt, const N: usize, P: FloorPlanner> Circuit<F> for RepeatedLessThanCircuit<F, N, P> {
+    type Config = RepeatedLessThanConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            repeats: self.repeats,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> RepeatedLessThanConfig {
+        let less_than = LessThanChip::configure(meta, N);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        RepeatedLessThanConfig {
+            less_than,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: RepeatedLessThanConfig,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = LessThanChip::new(config.less_than);
+
+        // Every repeat compares the same inputs, as the components of the circom template
+        let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+        let b = chip.load_private(layouter.namespace(|| "load b"), self.b)?;
+        for repeat in 0..self.repeats {
+            let out = chip.less_than(layouter.namespace(|| "less than"), &a, &b)?;
+            if repeat == 0 {
+                layouter.constrain_instance(out.cell(), config.instance, 0)?;
+            }
+        }
+
+        Ok(())
+    }
+}
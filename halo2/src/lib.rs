@@ -0,0 +1,26 @@
+//! Halo2 circuits that are benched against their circom/Groth16 equivalents.
+//!
+//! Every circuit used by the benches in `benches/` and the tests in `tests/` lives in this crate so
+//! that there is a single implementation of each of them. The crate also holds a native Groth16
+//! prover (see [`groth16`]) running R1CS ports of the circom circuits.
+
+pub mod cache;
+pub mod circom;
+pub mod ecdsa;
+pub mod fit;
+pub mod groth16;
+pub mod ipa;
+pub mod iszero;
+pub mod iszero_chip;
+pub mod layout;
+pub mod less_than;
+pub mod memory;
+pub mod num2bits;
+pub mod prover;
+pub mod r1cs;
+pub mod r1cs_circuit;
+pub mod range;
+pub mod results;
+pub mod standard_plonk;
+pub mod transcript;
+pub mod tutorial;
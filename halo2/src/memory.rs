@@ -0,0 +1,105 @@
+//! Memory used by the phases of the benches, measured by a global allocator counting the bytes it
+//! hands out.
+//!
+//! [`CountingAllocator`] only counts when it is the global allocator of the binary, which the bench
+//! harness declares with
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! Counters are shared by every thread, so [`measure`] sees the allocations of rayon's workers as
+//! well, and measurements shouldn't overlap. The peak is that of the heap, which at the sizes
+//! benched is nearly all of the resident memory of the process.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+/// Bytes currently allocated
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Largest value of `CURRENT` since the last [`measure`] started
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated since the start of the process, freed or not
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// Allocator forwarding to the system one while counting the bytes allocated
+pub struct CountingAllocator;
+
+fn allocated(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+fn freed(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                allocated(new_size - layout.size());
+            } else {
+                freed(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Memory used by one run of a phase
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Memory {
+    /// Largest amount of heap memory in use during the phase, above what was in use when it
+    /// started
+    pub peak_bytes: usize,
+    /// Total size of the allocations made during the phase, freed or not
+    pub allocated_bytes: u64,
+    /// Heap memory still held when the phase ended, i.e. by its output: the size of the proving
+    /// key for prover key generation
+    pub retained_bytes: usize,
+}
+
+/// Runs `f`, returning its output along with the memory it used. Everything is zero unless
+/// [`CountingAllocator`] is the global allocator.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Memory) {
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+
+    let output = f();
+
+    let memory = Memory {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+        allocated_bytes: TOTAL.load(Ordering::Relaxed) - total,
+        retained_bytes: CURRENT.load(Ordering::Relaxed).saturating_sub(start),
+    };
+    (output, memory)
+}
@@ -0,0 +1,168 @@
+//! circomlib's `Num2Bits(n)` as a chip: the decomposition of a value into `n` boolean constrained
+//! bits, least significant first.
+//!
+//! The bits are laid out one per row next to a running sum, from the value itself on the first row
+//! down to the most significant bit on the last: every row but the last checks
+//! `acc = 2 * acc_next + bit`, and the last `acc = bit`, so the value is `sum(bit_i * 2^i)`. Where
+//! the circom template has `n` boolean constraints and a linear one, the chip takes `n` rows.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Chip, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+/// Returns the `i`th bit of `value`, that of weight `2^i`
+pub(crate) fn bit<F: FieldExt>(value: &F, i: usize) -> F {
+    // The representation of the field elements of halo2curves is little endian
+    let repr = value.to_repr();
+    let byte = repr.as_ref().get(i / 8).copied().unwrap_or(0);
+    F::from(u64::from((byte >> (i % 8)) & 1))
+}
+
+/// Columns of the bit decomposition gates
+#[derive(Clone, Debug)]
+pub struct Num2BitsConfig {
+    pub bit: Column<Advice>,
+    pub acc: Column<Advice>,
+
+    /// Enabled on every row but the last one of a decomposition
+    pub q_step: Selector,
+    /// Enabled on the last row of a decomposition, that of the most significant bit
+    pub q_top: Selector,
+}
+
+/// Instructions of a chip decomposing values into bits
+pub trait Num2BitsInstructions<F: FieldExt>: Chip<F> {
+    /// Variable holding a value in the circuit
+    type Num;
+
+    /// Decomposes `value` into `n` bits, returned least significant first. The constraints only
+    /// hold if `value` fits in `n` bits.
+    fn num2bits(
+        &self,
+        layouter: impl Layouter<F>,
+        value: &Self::Num,
+        n: usize,
+    ) -> Result<Vec<Self::Num>, Error>;
+}
+
+/// Chip implementing [`Num2BitsInstructions`] with one region of `n` rows per decomposition
+pub struct Num2BitsChip<F: FieldExt> {
+    config: Num2BitsConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Num2BitsChip<F> {
+    pub fn new(config: Num2BitsConfig) -> Self {
+        Num2BitsChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allocates the two advice columns and the selectors and creates the "num2bits" gates
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> Num2BitsConfig {
+        let bit = meta.advice_column();
+        let acc = meta.advice_column();
+
+        // The value is copied to the first running sum, and the bits can be copied to other chips
+        meta.enable_equality(bit);
+        meta.enable_equality(acc);
+
+        let q_step = meta.selector();
+        let q_top = meta.selector();
+
+        meta.create_gate("num2bits step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let bit = meta.query_advice(bit, Rotation::cur());
+            let acc_next = meta.query_advice(acc, Rotation::next());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+
+            vec![
+                // bit * (bit - 1) = 0
+                q_step.clone() * bit.clone() * (bit.clone() - one),
+                // acc = 2 * acc_next + bit
+                q_step * (acc - two * acc_next - bit),
+            ]
+        });
+
+        meta.create_gate("num2bits top", |meta| {
+            let q_top = meta.query_selector(q_top);
+            let bit = meta.query_advice(bit, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // bit * (bit - 1) = 0
+                q_top.clone() * bit.clone() * (bit.clone() - one),
+                // acc = bit
+                q_top * (acc - bit),
+            ]
+        });
+
+        Num2BitsConfig {
+            bit,
+            acc,
+            q_step,
+            q_top,
+        }
+    }
+}
+
+impl<F: FieldExt> Chip<F> for Num2BitsChip<F> {
+    type Config = Num2BitsConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Num2BitsInstructions<F> for Num2BitsChip<F> {
+    type Num = AssignedCell<F, F>;
+
+    fn num2bits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &Self::Num,
+        n: usize,
+    ) -> Result<Vec<Self::Num>, Error> {
+        assert!(n > 0, "values should be decomposed into at least one bit");
+
+        layouter.assign_region(
+            || "num2bits",
+            |mut region| {
+                let bits: Vec<Value<F>> = (0..n)
+                    .map(|i| value.value().map(|value| bit(value, i)))
+                    .collect();
+
+                value.copy_advice(|| "in", &mut region, self.config.acc, 0)?;
+                let mut acc = value.value().copied();
+                let mut cells = Vec::with_capacity(n);
+                for (row, bit) in bits.iter().enumerate() {
+                    cells.push(region.assign_advice(|| "bit", self.config.bit, row, || *bit)?);
+
+                    if row + 1 == n {
+                        self.config.q_top.enable(&mut region, row)?;
+                    } else {
+                        self.config.q_step.enable(&mut region, row)?;
+                        // acc_next = (acc - bit) / 2
+                        acc = acc.zip(*bit).map(|(acc, bit)| (acc - bit) * F::TWO_INV);
+                        region.assign_advice(|| "acc", self.config.acc, row + 1, || acc)?;
+                    }
+                }
+
+                Ok(cells)
+            },
+        )
+    }
+}
@@ -0,0 +1,363 @@
+//! Thin wrappers around the KZG key generation, proving and verification functions of
+//! `halo2_proofs`, shared by the benches and the tests.
+//!
+//! Proofs open their commitments with either of the two multi-open arguments `halo2_proofs` ships
+//! for KZG, see [`MultiOpen`], and derive their challenges with one of the transcripts of
+//! [`crate::transcript`]; [`prove`] and [`verify`] use GWC and Blake2b.
+
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{
+        create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, Error, ProvingKey, VerifyingKey,
+    },
+    poly::{
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverGWC, ProverSHPLONK, VerifierGWC, VerifierSHPLONK},
+            strategy::{AccumulatorStrategy, SingleStrategy},
+        },
+        VerificationStrategy,
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+};
+use rand_core::OsRng;
+
+use crate::transcript::{
+    Keccak256Read, Keccak256Write, PoseidonRead, PoseidonWrite, TranscriptHash,
+};
+
+/// Generates the verifying key, and from it the proving key, of `circuit`
+pub fn keygen<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    circuit: &C,
+) -> Result<ProvingKey<G1Affine>, Error> {
+    let vk = keygen_vk(params, circuit)?;
+    keygen_pk(params, vk, circuit)
+}
+
+/// Multi-open argument a proof opens its polynomial commitments with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MultiOpen {
+    /// The argument of the PLONK paper (Gabizon, Williamson and Ciobotaru), with one opening
+    /// proof per point the polynomials are evaluated at
+    Gwc,
+    /// The argument of Boneh, Drake, Fisch and Gabizon, whose opening proof is two points
+    /// whatever the number of evaluation points, at the cost of more work for the prover
+    Shplonk,
+}
+
+impl MultiOpen {
+    pub const ALL: [MultiOpen; 2] = [MultiOpen::Gwc, MultiOpen::Shplonk];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MultiOpen::Gwc => "GWC",
+            MultiOpen::Shplonk => "SHPLONK",
+        }
+    }
+
+    /// Parses a name as returned by [`MultiOpen::name`], ignoring case
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|multiopen| multiopen.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Proves `circuits` with the GWC multi-open scheme and a Blake2b transcript, returning the proof.
+///
+/// `instances` holds, for every circuit, the values of each of its instance columns.
+pub fn prove<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuits: &[C],
+    instances: &[&[&[Fr]]],
+) -> Result<Vec<u8>, Error> {
+    prove_with(MultiOpen::Gwc, params, pk, circuits, instances)
+}
+
+/// Same as [`prove`] with the multi-open scheme `multiopen`
+pub fn prove_with<C: Circuit<Fr>>(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuits: &[C],
+    instances: &[&[&[Fr]]],
+) -> Result<Vec<u8>, Error> {
+    prove_with_transcript(
+        multiopen,
+        TranscriptHash::Blake2b,
+        params,
+        pk,
+        circuits,
+        instances,
+    )
+}
+
+/// Same as [`prove_with`] with a transcript hashing with `transcript`
+pub fn prove_with_transcript<C: Circuit<Fr>>(
+    multiopen: MultiOpen,
+    transcript: TranscriptHash,
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuits: &[C],
+    instances: &[&[&[Fr]]],
+) -> Result<Vec<u8>, Error> {
+    match transcript {
+        TranscriptHash::Blake2b => prove_into::<
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            C,
+        >(multiopen, params, pk, circuits, instances),
+        TranscriptHash::Keccak256 => prove_into::<Keccak256Write<Vec<u8>, G1Affine>, C>(
+            multiopen, params, pk, circuits, instances,
+        ),
+        TranscriptHash::Poseidon => {
+            prove_into::<PoseidonWrite<Vec<u8>>, C>(multiopen, params, pk, circuits, instances)
+        }
+    }
+}
+
+/// Proves `circuits` into a transcript of type `T`
+fn prove_into<T, C>(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuits: &[C],
+    instances: &[&[&[Fr]]],
+) -> Result<Vec<u8>, Error>
+where
+    T: TranscriptWriterBuffer<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+    C: Circuit<Fr>,
+{
+    let mut transcript = T::init(vec![]);
+    match multiopen {
+        MultiOpen::Gwc => create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+            params,
+            pk,
+            circuits,
+            instances,
+            OsRng,
+            &mut transcript,
+        )?,
+        MultiOpen::Shplonk => create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<Bn256>,
+            _,
+            _,
+            _,
+            _,
+        >(params, pk, circuits, instances, OsRng, &mut transcript)?,
+    }
+    Ok(transcript.finalize())
+}
+
+/// Verifies a proof created by [`prove`]
+pub fn verify(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[&[&[Fr]]],
+    proof: &[u8],
+) -> Result<(), Error> {
+    verify_with(MultiOpen::Gwc, params, vk, instances, proof)
+}
+
+/// Verifies a proof created by [`prove_with`] with the multi-open scheme `multiopen`
+pub fn verify_with(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[&[&[Fr]]],
+    proof: &[u8],
+) -> Result<(), Error> {
+    verify_with_transcript(
+        multiopen,
+        TranscriptHash::Blake2b,
+        params,
+        vk,
+        instances,
+        proof,
+    )
+}
+
+/// Verifies a proof created by [`prove_with_transcript`] with the multi-open scheme `multiopen`
+/// and a transcript hashing with `transcript`
+pub fn verify_with_transcript(
+    multiopen: MultiOpen,
+    transcript: TranscriptHash,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[&[&[Fr]]],
+    proof: &[u8],
+) -> Result<(), Error> {
+    match transcript {
+        TranscriptHash::Blake2b => verify_from::<Blake2bRead<_, G1Affine, Challenge255<G1Affine>>>(
+            multiopen, params, vk, instances, proof,
+        ),
+        TranscriptHash::Keccak256 => {
+            verify_from::<Keccak256Read<_, G1Affine>>(multiopen, params, vk, instances, proof)
+        }
+        TranscriptHash::Poseidon => {
+            verify_from::<PoseidonRead<_>>(multiopen, params, vk, instances, proof)
+        }
+    }
+}
+
+/// Verifies a proof read from a transcript of type `T`
+fn verify_from<'a, T>(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[&[&[Fr]]],
+    proof: &'a [u8],
+) -> Result<(), Error>
+where
+    T: TranscriptReadBuffer<&'a [u8], G1Affine, Challenge255<G1Affine>>,
+{
+    let strategy = SingleStrategy::new(params);
+    let mut transcript = T::init(proof);
+    match multiopen {
+        MultiOpen::Gwc => verify_proof::<_, VerifierGWC<Bn256>, _, _, _>(
+            params,
+            vk,
+            strategy,
+            instances,
+            &mut transcript,
+        ),
+        MultiOpen::Shplonk => verify_proof::<_, VerifierSHPLONK<Bn256>, _, _, _>(
+            params,
+            vk,
+            strategy,
+            instances,
+            &mut transcript,
+        ),
+    }
+}
+
+/// Proves `circuits`, independent instances of the same circuit, in a single proof. The instance
+/// columns of `circuits[i]` hold `instances[i]`.
+pub fn prove_many<C: Circuit<Fr>>(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuits: &[C],
+    instances: &[Vec<Vec<Fr>>],
+) -> Result<Vec<u8>, Error> {
+    if circuits.len() != instances.len() {
+        return Err(Error::InvalidInstances);
+    }
+    let columns: Vec<Vec<&[Fr]>> = instances
+        .iter()
+        .map(|instances| instance_refs(instances))
+        .collect();
+    let instances: Vec<&[&[Fr]]> = columns.iter().map(Vec::as_slice).collect();
+    prove_with(multiopen, params, pk, circuits, &instances)
+}
+
+/// Verifies a proof created by [`prove_many`], whose `i`th circuit has the instance columns
+/// `instances[i]`
+pub fn verify_many(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Vec<Fr>>],
+    proof: &[u8],
+) -> Result<(), Error> {
+    let columns: Vec<Vec<&[Fr]>> = instances
+        .iter()
+        .map(|instances| instance_refs(instances))
+        .collect();
+    let instances: Vec<&[&[Fr]]> = columns.iter().map(Vec::as_slice).collect();
+    verify_with(multiopen, params, vk, &instances, proof)
+}
+
+/// Verifies proofs created by [`prove_with_transcript`] with the multi-open scheme `multiopen`
+/// and the transcript `transcript`, each along with the values of its instance columns, as a
+/// batch.
+///
+/// Every proof is reduced to an accumulated pair of MSMs, and the pairing check that ends the
+/// verification of a single proof is only done once, on the random linear combination of all of
+/// them. The batch fails as a whole: it doesn't tell which proof is invalid.
+pub fn verify_batch(
+    multiopen: MultiOpen,
+    transcript: TranscriptHash,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    proofs: &[(&[&[&[Fr]]], &[u8])],
+) -> Result<(), Error> {
+    match transcript {
+        TranscriptHash::Blake2b => verify_batch_from::<
+            Blake2bRead<_, G1Affine, Challenge255<G1Affine>>,
+        >(multiopen, params, vk, proofs),
+        TranscriptHash::Keccak256 => {
+            verify_batch_from::<Keccak256Read<_, G1Affine>>(multiopen, params, vk, proofs)
+        }
+        TranscriptHash::Poseidon => {
+            verify_batch_from::<PoseidonRead<_>>(multiopen, params, vk, proofs)
+        }
+    }
+}
+
+/// Verifies a batch of proofs read from transcripts of type `T`
+fn verify_batch_from<'a, T>(
+    multiopen: MultiOpen,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    proofs: &[(&[&[&[Fr]]], &'a [u8])],
+) -> Result<(), Error>
+where
+    T: TranscriptReadBuffer<&'a [u8], G1Affine, Challenge255<G1Affine>>,
+{
+    let mut strategy = AccumulatorStrategy::new(params);
+    for &(instances, proof) in proofs {
+        let mut transcript = T::init(proof);
+        strategy = match multiopen {
+            MultiOpen::Gwc => verify_proof::<_, VerifierGWC<Bn256>, _, _, _>(
+                params,
+                vk,
+                strategy,
+                instances,
+                &mut transcript,
+            )?,
+            MultiOpen::Shplonk => verify_proof::<_, VerifierSHPLONK<Bn256>, _, _, _>(
+                params,
+                vk,
+                strategy,
+                instances,
+                &mut transcript,
+            )?,
+        };
+    }
+
+    let valid = match multiopen {
+        MultiOpen::Gwc => <AccumulatorStrategy<Bn256> as VerificationStrategy<
+            KZGCommitmentScheme<Bn256>,
+            VerifierGWC<Bn256>,
+        >>::finalize(strategy),
+        MultiOpen::Shplonk => <AccumulatorStrategy<Bn256> as VerificationStrategy<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<Bn256>,
+        >>::finalize(strategy),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::ConstraintSystemFailure)
+    }
+}
+
+/// Name the results of proofs made with `multiopen` and `transcript` are recorded under: the name
+/// of the scheme, followed by the one of the transcript unless it is the default Blake2b
+pub fn variant_name(multiopen: MultiOpen, transcript: TranscriptHash) -> String {
+    match transcript {
+        TranscriptHash::Blake2b => multiopen.name().to_string(),
+        _ => format!("{} {}", multiopen.name(), transcript.name()),
+    }
+}
+
+/// Borrows the instance columns of a single circuit in the shape expected by [`prove`] and
+/// [`verify`] (after wrapping in one more slice)
+pub fn instance_refs<F>(instances: &[Vec<F>]) -> Vec<&[F]> {
+    instances.iter().map(|column| column.as_slice()).collect()
+}
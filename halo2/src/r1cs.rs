@@ -0,0 +1,250 @@
+//! Rank-1 constraint systems, the constraint systems circom compiles to and Groth16 proves.
+//!
+//! Wires are laid out the way circom lays them out: wire 0 always holds the constant 1, followed
+//! by the public wires (outputs, then public inputs) and finally every private wire. A witness is
+//! the value of every wire, including the constant one.
+
+use ff::PrimeField;
+use std::fmt;
+
+/// Sum of `coefficient * wire` terms
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinearCombination<F: PrimeField> {
+    pub terms: Vec<(usize, F)>,
+}
+
+impl<F: PrimeField> LinearCombination<F> {
+    /// The empty combination, evaluating to zero
+    pub fn zero() -> Self {
+        Self { terms: vec![] }
+    }
+
+    /// The combination holding the constant `value`
+    pub fn constant(value: F) -> Self {
+        Self::zero().add(0, value)
+    }
+
+    /// The combination holding a single wire
+    pub fn wire(wire: usize) -> Self {
+        Self::zero().add(wire, F::one())
+    }
+
+    /// Adds `coefficient * wire` to the combination
+    pub fn add(mut self, wire: usize, coefficient: F) -> Self {
+        self.terms.push((wire, coefficient));
+        self
+    }
+
+    /// Value of the combination under `witness`
+    pub fn evaluate(&self, witness: &[F]) -> F {
+        self.terms
+            .iter()
+            .fold(F::zero(), |acc, (wire, coefficient)| {
+                acc + witness[*wire] * coefficient
+            })
+    }
+}
+
+/// The constraint `a * b = c`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constraint<F: PrimeField> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+}
+
+impl<F: PrimeField> Constraint<F> {
+    /// Returns whether `witness` satisfies the constraint
+    pub fn is_satisfied(&self, witness: &[F]) -> bool {
+        self.a.evaluate(witness) * self.b.evaluate(witness) == self.c.evaluate(witness)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1cs<F: PrimeField> {
+    /// Number of public wires, not counting the constant one
+    pub num_public: usize,
+    /// Number of wires, including the constant one
+    pub num_wires: usize,
+    pub constraints: Vec<Constraint<F>>,
+    /// Label of every wire, which for circom circuits maps it to a signal of the `.sym` file
+    pub wire_labels: Vec<u64>,
+}
+
+impl<F: PrimeField> R1cs<F> {
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Values of the public wires of `witness`, i.e. the public inputs of a proof
+    pub fn public_inputs<'a>(&self, witness: &'a [F]) -> &'a [F] {
+        &witness[1..=self.num_public]
+    }
+}
+
+/// Value of a wire involved in an unsatisfied constraint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WireValue<F: PrimeField> {
+    pub wire: usize,
+    pub label: u64,
+    pub value: F,
+}
+
+/// The reason a witness doesn't satisfy an [`R1cs`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum R1csFailure<F: PrimeField> {
+    /// The witness doesn't have one value per wire
+    WitnessLength { expected: usize, actual: usize },
+    /// Wire 0 doesn't hold the constant one
+    ConstantWire { value: F },
+    /// `a * b != c` for the constraint at index `constraint`
+    ConstraintNotSatisfied {
+        constraint: usize,
+        a: F,
+        b: F,
+        c: F,
+        /// Every wire the constraint involves, in order of appearance
+        wires: Vec<WireValue<F>>,
+    },
+}
+
+impl<F: PrimeField> fmt::Display for R1csFailure<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WitnessLength { expected, actual } => write!(
+                f,
+                "Witness has {} values but the constraint system has {} wires",
+                actual, expected
+            ),
+            Self::ConstantWire { value } => {
+                write!(f, "Wire 0 should hold the constant one, found {:?}", value)
+            }
+            Self::ConstraintNotSatisfied {
+                constraint,
+                a,
+                b,
+                c,
+                wires,
+            } => {
+                writeln!(
+                    f,
+                    "Constraint {} is not satisfied: {:?} * {:?} != {:?}",
+                    constraint, a, b, c
+                )?;
+                for wire in wires.iter() {
+                    writeln!(
+                        f,
+                        "  wire {} (label {}) = {:?}",
+                        wire.wire, wire.label, wire.value
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Checks that `witness` satisfies every constraint of `r1cs`, reporting the first constraint that
+/// isn't satisfied along with the labels and values of its wires
+pub fn check_r1cs<F: PrimeField>(r1cs: &R1cs<F>, witness: &[F]) -> Result<(), R1csFailure<F>> {
+    if witness.len() != r1cs.num_wires {
+        return Err(R1csFailure::WitnessLength {
+            expected: r1cs.num_wires,
+            actual: witness.len(),
+        });
+    }
+    if witness[0] != F::one() {
+        return Err(R1csFailure::ConstantWire { value: witness[0] });
+    }
+
+    for (index, constraint) in r1cs.constraints.iter().enumerate() {
+        if constraint.is_satisfied(witness) {
+            continue;
+        }
+
+        let mut wires: Vec<WireValue<F>> = vec![];
+        for (wire, _) in [&constraint.a, &constraint.b, &constraint.c]
+            .iter()
+            .flat_map(|lc| lc.terms.iter())
+        {
+            if !wires.iter().any(|value| value.wire == *wire) {
+                wires.push(WireValue {
+                    wire: *wire,
+                    label: r1cs.wire_labels.get(*wire).copied().unwrap_or(*wire as u64),
+                    value: witness[*wire],
+                });
+            }
+        }
+        return Err(R1csFailure::ConstraintNotSatisfied {
+            constraint: index,
+            a: constraint.a.evaluate(witness),
+            b: constraint.b.evaluate(witness),
+            c: constraint.c.evaluate(witness),
+            wires,
+        });
+    }
+    Ok(())
+}
+
+/// Builds an [`R1cs`] along with a witness satisfying it
+#[derive(Debug)]
+pub struct R1csBuilder<F: PrimeField> {
+    r1cs: R1cs<F>,
+    witness: Vec<F>,
+}
+
+impl<F: PrimeField> Default for R1csBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> R1csBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            r1cs: R1cs {
+                num_public: 0,
+                num_wires: 1,
+                constraints: vec![],
+                wire_labels: vec![0],
+            },
+            witness: vec![F::one()],
+        }
+    }
+
+    /// Allocates a public wire holding `value`. Public wires come first, so this panics once a
+    /// private wire has been allocated.
+    pub fn public(&mut self, value: F) -> usize {
+        assert_eq!(
+            self.r1cs.num_wires,
+            self.r1cs.num_public + 1,
+            "public wires should be allocated before private ones"
+        );
+        self.r1cs.num_public += 1;
+        self.private(value)
+    }
+
+    /// Allocates a private wire holding `value`, labelled with its index
+    pub fn private(&mut self, value: F) -> usize {
+        let wire = self.r1cs.num_wires;
+        self.witness.push(value);
+        self.r1cs.wire_labels.push(wire as u64);
+        self.r1cs.num_wires += 1;
+        wire
+    }
+
+    /// Adds the constraint `a * b = c`
+    pub fn enforce(
+        &mut self,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    ) {
+        self.r1cs.constraints.push(Constraint { a, b, c });
+    }
+
+    /// Returns the constraint system and its witness
+    pub fn build(self) -> (R1cs<F>, Vec<F>) {
+        (self.r1cs, self.witness)
+    }
+}
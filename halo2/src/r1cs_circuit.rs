@@ -0,0 +1,287 @@
+//! Halo2 circuit proving any [`R1cs`], e.g. one read from a circom `.r1cs` file, so that both
+//! proving systems can be compared on exactly the same constraint system.
+//!
+//! Like the "mini plonk" gate of [`crate::standard_plonk`], a single custom gate does all the
+//! work, but it is shaped after R1CS constraints rather than PLONK ones. Three lanes, one for each
+//! of `A`, `B` and `C`, hold a term `coefficient * wire` per row and the running sum of the terms
+//! of their linear combination, and the last row of every constraint checks that
+//! `sum_a * sum_b = sum_c`. A constraint therefore takes as many rows as its longest linear
+//! combination.
+//!
+//! Every time a wire appears in a term, its cell is copy constrained to the first cell holding
+//! it. The constant one and the public wires are laid out first, the former being fixed to one and
+//! the latter exposed in the instance column, in order.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Cell, Layouter, SimpleFloorPlanner, Value},
+    plonk::{
+        Advice, Circuit, Column, ConstraintSystem, Error, Expression, Fixed, FloorPlanner,
+        Instance, Selector,
+    },
+    poly::Rotation,
+};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use crate::r1cs::R1cs;
+
+/// Columns of the R1CS gate, the arrays holding one column per lane (`A`, `B` and `C`)
+#[derive(Clone, Debug)]
+pub struct R1csConfig {
+    /// Wire of the term of every row
+    pub wires: [Column<Advice>; 3],
+    /// Running sum of the terms of the current constraint
+    pub sums: [Column<Advice>; 3],
+    /// Coefficient of the term of every row
+    pub coefficients: [Column<Fixed>; 3],
+    /// 1 on every row of a constraint but its first, where the sums start over
+    pub continues: Column<Fixed>,
+    /// Fixed column the constant one is copied from
+    pub constant: Column<Fixed>,
+    pub instance: Column<Instance>,
+    pub q_sum: Selector,
+    pub q_mul: Selector,
+}
+
+impl R1csConfig {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        let wires = [(); 3].map(|_| meta.advice_column());
+        let sums = [(); 3].map(|_| meta.advice_column());
+        let coefficients = [(); 3].map(|_| meta.fixed_column());
+        let continues = meta.fixed_column();
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+        let q_sum = meta.selector();
+        let q_mul = meta.selector();
+
+        for wire in wires {
+            meta.enable_equality(wire);
+        }
+        meta.enable_equality(instance);
+        meta.enable_constant(constant);
+
+        meta.create_gate("r1cs sum", |meta| {
+            let q_sum = meta.query_selector(q_sum);
+            let continues = meta.query_fixed(continues, Rotation::cur());
+            (0..3)
+                .map(|lane| {
+                    let wire = meta.query_advice(wires[lane], Rotation::cur());
+                    let coefficient = meta.query_fixed(coefficients[lane], Rotation::cur());
+                    let sum = meta.query_advice(sums[lane], Rotation::cur());
+                    let previous = meta.query_advice(sums[lane], Rotation::prev());
+                    q_sum.clone() * (sum - continues.clone() * previous - coefficient * wire)
+                })
+                .collect::<Vec<Expression<F>>>()
+        });
+
+        meta.create_gate("r1cs mul", |meta| {
+            let q_mul = meta.query_selector(q_mul);
+            let [a, b, c] = sums.map(|sum| meta.query_advice(sum, Rotation::cur()));
+            vec![q_mul * (a * b - c)]
+        });
+
+        Self {
+            wires,
+            sums,
+            coefficients,
+            continues,
+            constant,
+            instance,
+            q_sum,
+            q_mul,
+        }
+    }
+}
+
+/// Circuit proving that `witness` satisfies `r1cs`, with the public wires as instance values,
+/// laid out by the floor planner `P`
+#[derive(Debug)]
+pub struct R1csCircuit<F: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub r1cs: Arc<R1cs<F>>,
+    /// Value of every wire, including the constant one
+    pub witness: Value<Arc<Vec<F>>>,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt> R1csCircuit<F> {
+    pub fn new(r1cs: R1cs<F>, witness: Vec<F>) -> Self {
+        Self {
+            r1cs: Arc::new(r1cs),
+            witness: Value::known(Arc::new(witness)),
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Clone for R1csCircuit<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            r1cs: self.r1cs.clone(),
+            witness: self.witness.clone(),
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> R1csCircuit<F, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> R1csCircuit<F, Q> {
+        R1csCircuit {
+            r1cs: self.r1cs,
+            witness: self.witness,
+            _planner: PhantomData,
+        }
+    }
+
+    /// Values of the instance column, i.e. the public wires of the witness
+    pub fn instances(&self) -> Vec<Vec<F>> {
+        let mut public = vec![];
+        self.witness.as_ref().map(|witness| {
+            public = self.r1cs.public_inputs(witness).to_vec();
+        });
+        vec![public]
+    }
+
+    /// Number of rows taken by the circuit
+    pub fn num_rows(&self) -> usize {
+        self.r1cs.num_public
+            + 1
+            + self
+                .r1cs
+                .constraints
+                .iter()
+                .map(|constraint| {
+                    constraint
+                        .a
+                        .terms
+                        .len()
+                        .max(constraint.b.terms.len())
+                        .max(constraint.c.terms.len())
+                        .max(1)
+                })
+                .sum::<usize>()
+    }
+
+    /// Smallest `k` such that the circuit fits in `2^k` rows
+    pub fn min_k(&self) -> u32 {
+        let mut cs = ConstraintSystem::default();
+        Self::configure(&mut cs);
+        let rows = self.num_rows() + cs.blinding_factors() + 1;
+        (1..).find(|&k| 1 << k >= rows).unwrap()
+    }
+
+    fn value(&self, wire: usize) -> Value<F> {
+        self.witness.as_ref().map(|witness| witness[wire])
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for R1csCircuit<F, P> {
+    type Config = R1csConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            r1cs: self.r1cs.clone(),
+            witness: Value::unknown(),
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> R1csConfig {
+        R1csConfig::configure(meta)
+    }
+
+    fn synthesize(&self, config: R1csConfig, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        let r1cs = &self.r1cs;
+
+        let public_cells = layouter.assign_region(
+            || "r1cs",
+            |mut region| {
+                // First cell holding each wire, which every other cell holding it is copied from
+                let mut cells: HashMap<usize, Cell> = HashMap::new();
+                let zero = Value::known(F::zero());
+
+                // The constant one and the public wires, outside of any constraint
+                for wire in 0..=r1cs.num_public {
+                    let cell = region.assign_advice(
+                        || "public",
+                        config.wires[0],
+                        wire,
+                        || self.value(wire),
+                    )?;
+                    for sum in config.sums {
+                        region.assign_advice(|| "sum", sum, wire, || zero)?;
+                    }
+                    cells.insert(wire, cell.cell());
+                }
+                region.constrain_constant(cells[&0], F::one())?;
+
+                let mut row = r1cs.num_public + 1;
+                for constraint in r1cs.constraints.iter() {
+                    let lanes = [&constraint.a, &constraint.b, &constraint.c];
+                    let rows = lanes.iter().map(|lc| lc.terms.len()).max().unwrap().max(1);
+                    let mut sums = [zero; 3];
+
+                    for offset in 0..rows {
+                        config.q_sum.enable(&mut region, row)?;
+                        if offset > 0 {
+                            region.assign_fixed(
+                                || "continues",
+                                config.continues,
+                                row,
+                                || Value::known(F::one()),
+                            )?;
+                        }
+
+                        for (lane, lc) in lanes.iter().enumerate() {
+                            // Lanes with fewer terms are padded with `0 * 0`
+                            let (value, coefficient) = match lc.terms.get(offset) {
+                                Some((wire, coefficient)) => (self.value(*wire), *coefficient),
+                                None => (zero, F::zero()),
+                            };
+                            let cell = region.assign_advice(
+                                || "wire",
+                                config.wires[lane],
+                                row,
+                                || value,
+                            )?;
+                            region.assign_fixed(
+                                || "coefficient",
+                                config.coefficients[lane],
+                                row,
+                                || Value::known(coefficient),
+                            )?;
+                            sums[lane] = sums[lane] + value * Value::known(coefficient);
+                            region.assign_advice(
+                                || "sum",
+                                config.sums[lane],
+                                row,
+                                || sums[lane],
+                            )?;
+
+                            if let Some((wire, _)) = lc.terms.get(offset) {
+                                match cells.get(wire) {
+                                    Some(first) => region.constrain_equal(*first, cell.cell())?,
+                                    None => {
+                                        cells.insert(*wire, cell.cell());
+                                    }
+                                }
+                            }
+                        }
+                        row += 1;
+                    }
+                    config.q_mul.enable(&mut region, row - 1)?;
+                }
+
+                Ok((1..=r1cs.num_public)
+                    .map(|wire| cells[&wire])
+                    .collect::<Vec<_>>())
+            },
+        )?;
+
+        for (index, cell) in public_cells.into_iter().enumerate() {
+            layouter.constrain_instance(cell, config.instance, index)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use halo2wrong::{
+    halo2::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        plonk::{Circuit, ConstraintSystem, Error, FloorPlanner},
+    },
+    RegionCtx,
+};
+use maingate::{
+    MainGate, MainGateConfig, MainGateInstructions, RangeChip, RangeConfig, RangeInstructions, Term,
+};
+use num_integer::Integer;
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::fit::max_repeats;
+
+/// Bit length of the limbs values are decomposed into
+pub const LIMB_BIT_LEN: usize = 8;
+
+/// Bit lengths of the most significant (overflow) limb that the lookup tables of a
+/// [`RangeCircuit`] support. Every bit length takes a table of its own, so they are part of the
+/// circuit being benched.
+pub trait OverflowBitLens: Clone + Debug + Default {
+    const OVERFLOW_BIT_LENS: &'static [usize];
+}
+
+/// The overflows of [`RangeCircuit::bench_inputs`], 2 bits, the only table of the benched circuit
+#[derive(Clone, Debug, Default)]
+pub struct BenchOverflow;
+
+impl OverflowBitLens for BenchOverflow {
+    const OVERFLOW_BIT_LENS: &'static [usize] = &[2];
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeCircuitConfig {
+    range_config: RangeConfig,
+    main_gate_config: MainGateConfig,
+}
+
+impl RangeCircuitConfig {
+    pub fn new<F: FieldExt>(
+        meta: &mut ConstraintSystem<F>,
+        composition_bit_lens: Vec<usize>,
+        overflow_bit_lens: Vec<usize>,
+    ) -> Self {
+        let main_gate_config = MainGate::<F>::configure(meta);
+
+        let range_config = RangeChip::<F>::configure(
+            meta,
+            &main_gate_config,
+            composition_bit_lens,
+            overflow_bit_lens,
+        );
+        Self {
+            range_config,
+            main_gate_config,
+        }
+    }
+
+    pub fn main_gate<F: FieldExt>(&self) -> MainGate<F> {
+        MainGate::<F>::new(self.main_gate_config.clone())
+    }
+
+    pub fn range_chip<F: FieldExt>(&self) -> RangeChip<F> {
+        RangeChip::<F>::new(self.range_config.clone())
+    }
+}
+
+/// A value claimed to fit in `bit_len` bits, decomposed into limbs of `limb_bit_len` bits
+#[derive(Clone, Debug)]
+pub struct RangeInput<F: FieldExt> {
+    pub bit_len: usize,
+    pub limb_bit_len: usize,
+    pub value: Value<F>,
+}
+
+impl<F: FieldExt> RangeInput<F> {
+    pub fn new(value: Value<F>, limb_bit_len: usize, bit_len: usize) -> Self {
+        Self {
+            bit_len,
+            limb_bit_len,
+            value,
+        }
+    }
+}
+
+/// Circuit range checking every input by decomposing it into limbs and recomposing it, with the
+/// overflow limbs supported by `O`, laid out by the floor planner `P`.
+///
+/// The whole set of inputs is checked `range_repeats` times in order to bench larger versions of
+/// the circuit (it simply repeats the computation).
+#[derive(Debug)]
+pub struct RangeCircuit<
+    F: FieldExt,
+    O: OverflowBitLens = BenchOverflow,
+    P: FloorPlanner = SimpleFloorPlanner,
+> {
+    pub inputs: Vec<RangeInput<F>>,
+    pub range_repeats: u32,
+    _overflow: PhantomData<O>,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt, O: OverflowBitLens> RangeCircuit<F, O> {
+    /// Creates a circuit whose lookup tables support the overflows of `O`
+    pub fn with_overflow(inputs: Vec<RangeInput<F>>, range_repeats: u32) -> Self {
+        Self {
+            inputs,
+            range_repeats,
+            _overflow: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, O: OverflowBitLens, P: FloorPlanner> RangeCircuit<F, O, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> RangeCircuit<F, O, Q> {
+        RangeCircuit {
+            inputs: self.inputs,
+            range_repeats: self.range_repeats,
+            _overflow: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+
+    fn composition_bit_lens(limb_bit_len: usize) -> Vec<usize> {
+        [limb_bit_len].to_vec()
+    }
+
+    fn overflow_bit_lens(overflow_bit_lens: &[usize]) -> Vec<usize> {
+        overflow_bit_lens.to_vec()
+    }
+}
+
+impl<F: FieldExt, O: OverflowBitLens, P: FloorPlanner> Clone for RangeCircuit<F, O, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            range_repeats: self.range_repeats,
+            _overflow: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, O: OverflowBitLens, P: FloorPlanner> Default for RangeCircuit<F, O, P> {
+    fn default() -> Self {
+        Self {
+            inputs: vec![],
+            range_repeats: 0,
+            _overflow: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> RangeCircuit<F> {
+    /// Creates the benched circuit, whose only lookup table is that of [`BenchOverflow`]
+    pub fn new(inputs: Vec<RangeInput<F>>, range_repeats: u32) -> Self {
+        Self::with_overflow(inputs, range_repeats)
+    }
+
+    /// The inputs used by the benches: values of `LIMB_BIT_LEN * n + 2` bits for `n` in `2..15`
+    pub fn bench_inputs() -> Vec<RangeInput<F>> {
+        (2..15)
+            .map(|number_of_limbs| {
+                let bit_len = LIMB_BIT_LEN * number_of_limbs + BenchOverflow::OVERFLOW_BIT_LENS[0];
+                RangeInput::new(
+                    Value::known(F::from_u128((1 << bit_len) - 1)),
+                    LIMB_BIT_LEN,
+                    bit_len,
+                )
+            })
+            .collect()
+    }
+
+    /// Largest number of repeats of [`RangeCircuit::bench_inputs`] that fits in a table of `2^k`
+    /// rows (alongside the lookup tables, which need at least 2^9 rows)
+    pub fn bench_repeats_for_k(k: u32) -> u32 {
+        let inputs = Self::bench_inputs();
+        let repeats = max_repeats(k, |repeats| {
+            (Self::new(inputs.clone(), repeats as u32), vec![vec![]])
+        });
+        assert!(repeats > 0, "the range circuit doesn't fit in 2^{} rows", k);
+        repeats as u32
+    }
+}
+
+impl<F: FieldExt, O: OverflowBitLens, P: FloorPlanner> Circuit<F> for RangeCircuit<F, O, P> {
+    type Config = RangeCircuitConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| RangeInput::new(Value::unknown(), input.limb_bit_len, input.bit_len))
+            .collect();
+        Self {
+            inputs,
+            range_repeats: self.range_repeats,
+            _overflow: PhantomData,
+            _planner: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        RangeCircuitConfig::new(
+            meta,
+            Self::composition_bit_lens(LIMB_BIT_LEN),
+            Self::overflow_bit_lens(O::OVERFLOW_BIT_LENS),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let range_chip = config.range_chip();
+        let main_gate = config.main_gate();
+
+        for _ in 0..self.range_repeats {
+            layouter.assign_region(
+                || "region 0",
+                |region| {
+                    let offset = 0;
+                    let ctx = &mut RegionCtx::new(region, offset);
+
+                    for input in self.inputs.iter() {
+                        let value = input.value;
+                        let limb_bit_len = input.limb_bit_len;
+                        let bit_len = input.bit_len;
+                        let (num_limbs, overflow_len) = bit_len.div_rem(&limb_bit_len);
+                        let num_bases = num_limbs + usize::from(overflow_len != 0);
+                        let bases: Vec<F> = (0..num_bases)
+                            .map(|i| F::from(2).pow(&[(limb_bit_len * i) as u64, 0, 0, 0]))
+                            .collect();
+
+                        let a_0 = main_gate.assign_value(ctx, value)?;
+                        let (a_1, decomposed) =
+                            range_chip.decompose(ctx, value, limb_bit_len, bit_len)?;
+
+                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+
+                        let terms: Vec<Term<F>> = decomposed
+                            .iter()
+                            .zip(bases.as_slice())
+                            .map(|(limb, base)| Term::Assigned(limb, *base))
+                            .collect();
+                        let a_1 = main_gate.compose(ctx, &terms[..], F::zero())?;
+                        main_gate.assert_equal(ctx, &a_0, &a_1)?;
+                    }
+
+                    Ok(())
+                },
+            )?;
+        }
+
+        range_chip.load_table(&mut layouter)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,464 @@
+//! Machine-readable bench results and the Halo2 vs Groth16 comparison report built from them.
+//!
+//! Results are stored as JSON lines, one [`PhaseResult`] per line, so that every bench binary
+//! (and the circom scripts in `groth16/`) can append to the same files. By default they live in
+//! `results/` at the root of the repository: `halo2.jsonl` for the Halo2 benches and
+//! `groth16.jsonl` for the Groth16 ones.
+
+use crate::memory::Memory;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::Write as _,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    thread,
+};
+
+/// Environment variable overriding the directory results are written to and read from
+pub const RESULTS_DIR_VAR: &str = "BENCH_RESULTS_DIR";
+/// Results directory used when `BENCH_RESULTS_DIR` isn't set, relative to the `halo2` crate
+pub const DEFAULT_RESULTS_DIR: &str = "../results";
+/// File (in the results directory) holding the Halo2 results
+pub const HALO2_RESULTS: &str = "halo2.jsonl";
+/// File (in the results directory) holding the Groth16 results
+pub const GROTH16_RESULTS: &str = "groth16.jsonl";
+
+/// Returns the directory results are written to and read from
+pub fn results_dir() -> PathBuf {
+    env::var(RESULTS_DIR_VAR)
+        .unwrap_or_else(|_| DEFAULT_RESULTS_DIR.to_string())
+        .into()
+}
+
+/// A step of the pipeline of a proving system, in the order they are run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// Compilation of the circuit (circom only)
+    Compile,
+    /// Witness generation (circom only, Halo2 generates it while proving)
+    Witness,
+    /// Generation of the transparent parameters (Halo2 IPA only, KZG parameters come from a
+    /// trusted setup)
+    Params,
+    /// Circuit specific setup (Groth16 only)
+    Setup,
+    /// Verifying key generation (Halo2 only)
+    KeygenVk,
+    /// Proving key generation (Halo2 only)
+    KeygenPk,
+    Prove,
+    Verify,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Compile => "Circuit Compilation",
+            Phase::Witness => "Witness Generation",
+            Phase::Params => "Parameter Generation",
+            Phase::Setup => "Circuit Set-up",
+            Phase::KeygenVk => "Verifier Key Generation",
+            Phase::KeygenPk => "Prover Key Generation",
+            Phase::Prove => "Proof Generation",
+            Phase::Verify => "Proof Verification",
+        }
+    }
+}
+
+/// The machine a result was recorded on
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Machine {
+    pub os: String,
+    pub arch: String,
+    pub threads: usize,
+}
+
+impl Machine {
+    /// Describes the machine this process runs on
+    pub fn current() -> Self {
+        Machine {
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            threads: thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+        }
+    }
+}
+
+/// Mean time taken by one phase of one proving system, for one circuit at size `2^k`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhaseResult {
+    /// Proving system, `halo2` or `groth16`
+    pub system: String,
+    pub circuit: String,
+    pub k: u32,
+    pub phase: Phase,
+    /// Configuration of the proving system the result was recorded with, e.g. the multi-open
+    /// scheme of Halo2. Results of different variants are reported side by side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub mean_ms: f64,
+    /// Size of the proof, only recorded alongside [`Phase::Prove`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_bytes: Option<usize>,
+    /// Number of public inputs (values of the instance columns for Halo2) of the proof, only
+    /// recorded alongside [`Phase::Prove`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_values: Option<usize>,
+    /// Serialized size of the verifying key, only recorded alongside the phase generating it
+    /// ([`Phase::KeygenVk`], or [`Phase::Setup`] for Groth16)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vk_bytes: Option<usize>,
+    /// Serialized size of the proving key, only recorded alongside the phase generating it
+    /// ([`Phase::KeygenPk`], or [`Phase::Setup`] for Groth16). Left empty for Halo2, whose proving
+    /// keys `halo2_proofs` can't serialise (nor expose the polynomials of) at this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pk_bytes: Option<usize>,
+    /// Serialized size of the parameters (the SRS for KZG) the circuit was set up with, only
+    /// recorded alongside the phase generating the proving key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub srs_bytes: Option<usize>,
+    /// Number of rows the Halo2 circuit uses out of its `2^k`, which depends on its floor planner,
+    /// only recorded alongside [`Phase::KeygenVk`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows_used: Option<usize>,
+    /// Memory used by one run of the phase, measured by `halo2::memory::CountingAllocator`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+    /// Number of threads of the rayon pool the phase ran on, when it was set explicitly rather
+    /// than left to all the threads of the machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+    pub machine: Machine,
+}
+
+impl PhaseResult {
+    /// Whether the phase ran on every thread of its machine
+    pub fn on_all_threads(&self) -> bool {
+        self.threads
+            .map_or(true, |threads| threads == self.machine.threads)
+    }
+}
+
+/// Appends `results` to the JSON lines file at `path`, creating it (and its directory) if needed
+pub fn append(path: &Path, results: &[PhaseResult]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for result in results {
+        let line = serde_json::to_string(result)?;
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Reads the results of the JSON lines file at `path`, or none if the file doesn't exist
+pub fn read(path: &Path) -> io::Result<Vec<PhaseResult>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut results = vec![];
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        results.push(serde_json::from_str(&line)?);
+    }
+    Ok(results)
+}
+
+/// Formats milliseconds the way criterion does, switching to seconds above one second
+pub fn format_ms(ms: f64) -> String {
+    if ms >= 1000.0 {
+        format!("{:.4} s", ms / 1000.0)
+    } else {
+        format!("{:.4} ms", ms)
+    }
+}
+
+/// Formats a number of bytes in the largest binary unit it holds at least one of
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+/// Latest result of every phase of one variant of a proving system for one circuit and size
+#[derive(Default)]
+struct Column {
+    phases: BTreeMap<Phase, f64>,
+    memory: BTreeMap<Phase, Memory>,
+    proof_bytes: Option<usize>,
+    instance_values: Option<usize>,
+    vk_bytes: Option<usize>,
+    pk_bytes: Option<usize>,
+    srs_bytes: Option<usize>,
+    rows_used: Option<usize>,
+    machines: BTreeSet<Machine>,
+}
+
+/// Builds a markdown report with one table per circuit and size, comparing the Halo2 and Groth16
+/// results side by side, with one column per variant of each proving system. When a phase was
+/// recorded several times the latest result is used. Results recorded on fewer threads than their
+/// machine has are left to [`scaling_report`].
+///
+/// The times are followed by the peak heap memory and the total allocations of every phase they
+/// were measured for, and by the sizes of the proof, of the verifying and proving keys and of the
+/// SRS, the number of public inputs and the number of rows used by Halo2 circuits.
+pub fn report(halo2: &[PhaseResult], groth16: &[PhaseResult]) -> String {
+    const SYSTEMS: [&str; 2] = ["Halo2", "Groth16"];
+
+    // Columns are keyed by system, then variant
+    type Columns = BTreeMap<(usize, Option<String>), Column>;
+    let mut tables: BTreeMap<(String, u32), Columns> = BTreeMap::new();
+    for (system, results) in [halo2, groth16].iter().enumerate() {
+        for result in results.iter().filter(|result| result.on_all_threads()) {
+            let column = tables
+                .entry((result.circuit.clone(), result.k))
+                .or_default()
+                .entry((system, result.variant.clone()))
+                .or_default();
+            column.phases.insert(result.phase, result.mean_ms);
+            if let Some(memory) = result.memory {
+                column.memory.insert(result.phase, memory);
+            }
+            if result.proof_bytes.is_some() {
+                column.proof_bytes = result.proof_bytes;
+            }
+            if result.instance_values.is_some() {
+                column.instance_values = result.instance_values;
+            }
+            if result.vk_bytes.is_some() {
+                column.vk_bytes = result.vk_bytes;
+            }
+            if result.pk_bytes.is_some() {
+                column.pk_bytes = result.pk_bytes;
+            }
+            if result.srs_bytes.is_some() {
+                column.srs_bytes = result.srs_bytes;
+            }
+            if result.rows_used.is_some() {
+                column.rows_used = result.rows_used;
+            }
+            column.machines.insert(result.machine.clone());
+        }
+    }
+
+    let mut report = String::new();
+    for ((circuit, k), columns) in tables.iter_mut() {
+        // Keep an empty column for a system without results
+        for system in 0..SYSTEMS.len() {
+            if !columns.keys().any(|(other, _)| *other == system) {
+                columns.insert((system, None), Column::default());
+            }
+        }
+        let titles: Vec<String> = columns
+            .keys()
+            .map(|(system, variant)| match variant {
+                Some(variant) => format!("{} {}", SYSTEMS[*system], variant),
+                None => SYSTEMS[*system].to_string(),
+            })
+            .collect();
+
+        writeln!(report, "### {} at 2^{} constraints\n", circuit, k).unwrap();
+        writeln!(report, "| Phase | {} |", titles.join(" | ")).unwrap();
+        writeln!(report, "|{}", " --- |".repeat(titles.len() + 1)).unwrap();
+
+        let phases: BTreeSet<Phase> = columns
+            .values()
+            .flat_map(|column| column.phases.keys().copied())
+            .collect();
+        for phase in phases {
+            let cells: Vec<String> = columns
+                .values()
+                .map(|column| {
+                    column
+                        .phases
+                        .get(&phase)
+                        .map_or_else(|| "-".to_string(), |ms| format_ms(*ms))
+                })
+                .collect();
+            writeln!(report, "| {} | {} |", phase.name(), cells.join(" | ")).unwrap();
+        }
+
+        let measured: BTreeSet<Phase> = columns
+            .values()
+            .flat_map(|column| column.memory.keys().copied())
+            .collect();
+        for phase in measured {
+            let peaks: Vec<String> = columns
+                .values()
+                .map(|column| {
+                    column.memory.get(&phase).map_or_else(
+                        || "-".to_string(),
+                        |memory| format_bytes(memory.peak_bytes as u64),
+                    )
+                })
+                .collect();
+            writeln!(
+                report,
+                "| {} Peak Memory | {} |",
+                phase.name(),
+                peaks.join(" | ")
+            )
+            .unwrap();
+            let allocated: Vec<String> = columns
+                .values()
+                .map(|column| {
+                    column.memory.get(&phase).map_or_else(
+                        || "-".to_string(),
+                        |memory| format_bytes(memory.allocated_bytes),
+                    )
+                })
+                .collect();
+            writeln!(
+                report,
+                "| {} Allocated | {} |",
+                phase.name(),
+                allocated.join(" | ")
+            )
+            .unwrap();
+        }
+
+        // Proofs and verifying keys are small enough to be compared byte for byte
+        let exact: fn(usize) -> String = |bytes| format!("{} B", bytes);
+        let binary: fn(usize) -> String = |bytes| format_bytes(bytes as u64);
+        let count: fn(usize) -> String = |values| values.to_string();
+        let values = |value: fn(&Column) -> Option<usize>| -> Vec<Option<usize>> {
+            columns.values().map(value).collect()
+        };
+        let rows = [
+            ("Proof Size", values(|column| column.proof_bytes), exact),
+            (
+                "Verifying Key Size",
+                values(|column| column.vk_bytes),
+                exact,
+            ),
+            ("Proving Key Size", values(|column| column.pk_bytes), binary),
+            ("SRS Size", values(|column| column.srs_bytes), binary),
+            (
+                "Public Inputs",
+                values(|column| column.instance_values),
+                count,
+            ),
+            ("Rows Used", values(|column| column.rows_used), count),
+        ];
+        for (row, values, format) in rows {
+            // Only the proof size is printed when nothing was recorded, results recorded before
+            // the other sizes were have none of them
+            if row != "Proof Size" && values.iter().all(Option::is_none) {
+                continue;
+            }
+            let cells: Vec<String> = values
+                .into_iter()
+                .map(|value| value.map_or_else(|| "-".to_string(), format))
+                .collect();
+            writeln!(report, "| {} | {} |", row, cells.join(" | ")).unwrap();
+        }
+        report.push('\n');
+
+        for (title, column) in titles.iter().zip(columns.values()) {
+            for machine in column.machines.iter() {
+                writeln!(
+                    report,
+                    "{} recorded on {} {} with {} threads  ",
+                    title, machine.os, machine.arch, machine.threads
+                )
+                .unwrap();
+            }
+        }
+        report.push('\n');
+    }
+    report
+}
+
+/// Builds a markdown report of how every phase scales with the number of threads, from the
+/// results recorded with an explicit thread pool: one table per proving system (and variant),
+/// circuit and size recorded at several thread counts, with one row per thread count. Every time
+/// is followed by its speedup over the smallest thread count recorded, and when a phase was
+/// recorded several times with the same number of threads the latest result is used.
+pub fn scaling_report(results: &[PhaseResult]) -> String {
+    // Tables are keyed by system, variant, circuit and size, rows by thread count
+    type Rows = BTreeMap<usize, BTreeMap<Phase, f64>>;
+    let mut tables: BTreeMap<(String, Option<String>, String, u32), Rows> = BTreeMap::new();
+    for result in results.iter() {
+        if let Some(threads) = result.threads {
+            tables
+                .entry((
+                    result.system.clone(),
+                    result.variant.clone(),
+                    result.circuit.clone(),
+                    result.k,
+                ))
+                .or_default()
+                .entry(threads)
+                .or_default()
+                .insert(result.phase, result.mean_ms);
+        }
+    }
+
+    let mut report = String::new();
+    for ((system, variant, circuit, k), rows) in tables.iter() {
+        // Only benched on the default pool of all the threads, see `THREADS` in the bench harness
+        if rows.len() < 2 {
+            continue;
+        }
+        let system = match system.as_str() {
+            "halo2" => "Halo2",
+            "groth16" => "Groth16",
+            system => system,
+        };
+        let system = match variant {
+            Some(variant) => format!("{} {}", system, variant),
+            None => system.to_string(),
+        };
+        let phases: BTreeSet<Phase> = rows
+            .values()
+            .flat_map(|phases| phases.keys().copied())
+            .collect();
+        let titles: Vec<&str> = phases.iter().map(|phase| phase.name()).collect();
+
+        writeln!(
+            report,
+            "### {} thread scaling, {} at 2^{} constraints\n",
+            system, circuit, k
+        )
+        .unwrap();
+        writeln!(report, "| Threads | {} |", titles.join(" | ")).unwrap();
+        writeln!(report, "|{}", " --- |".repeat(titles.len() + 1)).unwrap();
+        for (threads, times) in rows.iter() {
+            let cells: Vec<String> = phases
+                .iter()
+                .map(|phase| {
+                    let baseline = rows.values().find_map(|times| times.get(phase));
+                    match (times.get(phase), baseline) {
+                        (Some(ms), Some(baseline)) => {
+                            format!("{} ({:.2}x)", format_ms(*ms), baseline / ms)
+                        }
+                        _ => "-".to_string(),
+                    }
+                })
+                .collect();
+            writeln!(report, "| {} | {} |", threads, cells.join(" | ")).unwrap();
+        }
+        report.push('\n');
+    }
+    report
+}
@@ -0,0 +1,212 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Cell, Layouter, Value},
+    plonk::{Advice, Assigned, Column, ConstraintSystem, Error, Fixed},
+    poly::Rotation,
+};
+use std::{cell, marker::PhantomData};
+
+/// Columns of the "mini plonk" gate `a * sa + b * sb + a * b * sm - c * sc = 0`
+#[derive(Clone, Debug)]
+pub struct PlonkConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub c: Column<Advice>,
+
+    pub sa: Column<Fixed>,
+    pub sb: Column<Fixed>,
+    pub sc: Column<Fixed>,
+    pub sm: Column<Fixed>,
+}
+
+/// Instructions offered by a standard (vanilla) PLONK constraint system
+pub trait StandardCs<FF: FieldExt> {
+    /// Assigns `(a, b, c)` to a new row constrained by `a * b = c`
+    fn raw_multiply<F>(
+        &self,
+        layouter: &mut impl Layouter<FF>,
+        f: F,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        F: FnMut() -> Value<(Assigned<FF>, Assigned<FF>, Assigned<FF>)>;
+
+    /// Assigns `(a, b, c)` to a new row constrained by `a + b = c`
+    fn raw_add<F>(
+        &self,
+        layouter: &mut impl Layouter<FF>,
+        f: F,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        F: FnMut() -> Value<(Assigned<FF>, Assigned<FF>, Assigned<FF>)>;
+
+    /// Ensure two wire values are the same, in effect connecting the wires to each other
+    fn copy(&self, layouter: &mut impl Layouter<FF>, a: Cell, b: Cell) -> Result<(), Error>;
+}
+
+/// Chip implementing [`StandardCs`] with one region (and so one row) per gate.
+///
+/// A chip can have several lanes, copies of the columns of the gate (see
+/// [`StandardPlonk::configure_lanes`]). Gates then go round the lanes, and as regions using
+/// different columns can share rows the floor planner lays as many consecutive gates as there are
+/// lanes on the same row: fewer rows, for more columns.
+pub struct StandardPlonk<F: FieldExt> {
+    lanes: Vec<PlonkConfig>,
+    /// Lane of the next gate
+    next_lane: cell::Cell<usize>,
+    _marker: PhantomData<F>,
+}
+
+impl<FF: FieldExt> StandardPlonk<FF> {
+    pub fn new(config: PlonkConfig) -> Self {
+        Self::with_lanes(vec![config])
+    }
+
+    /// Creates a chip assigning its gates to `lanes` in turn
+    pub fn with_lanes(lanes: Vec<PlonkConfig>) -> Self {
+        assert!(!lanes.is_empty(), "a standard PLONK chip needs a lane");
+        StandardPlonk {
+            lanes,
+            next_lane: cell::Cell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Columns of the next gate, moving on to the following lane
+    fn lane(&self) -> &PlonkConfig {
+        let lane = self.next_lane.get();
+        self.next_lane.set((lane + 1) % self.lanes.len());
+        &self.lanes[lane]
+    }
+
+    /// Allocates the columns and creates the gate of every one of `width` lanes
+    pub fn configure_lanes(meta: &mut ConstraintSystem<FF>, width: usize) -> Vec<PlonkConfig> {
+        (0..width).map(|_| Self::configure(meta)).collect()
+    }
+
+    /// Allocates the three advice and four fixed columns and creates the "mini plonk" gate
+    pub fn configure(meta: &mut ConstraintSystem<FF>) -> PlonkConfig {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let c = meta.advice_column();
+
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(c);
+
+        let sm = meta.fixed_column();
+        let sa = meta.fixed_column();
+        let sb = meta.fixed_column();
+        let sc = meta.fixed_column();
+
+        meta.create_gate("mini plonk", |meta| {
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let c = meta.query_advice(c, Rotation::cur());
+
+            let sa = meta.query_fixed(sa, Rotation::cur());
+            let sb = meta.query_fixed(sb, Rotation::cur());
+            let sc = meta.query_fixed(sc, Rotation::cur());
+            let sm = meta.query_fixed(sm, Rotation::cur());
+
+            vec![a.clone() * sa + b.clone() * sb + a * b * sm + (c * sc * (-FF::one()))]
+        });
+
+        PlonkConfig {
+            a,
+            b,
+            c,
+            sa,
+            sb,
+            sc,
+            sm,
+        }
+    }
+}
+
+impl<FF: FieldExt> StandardCs<FF> for StandardPlonk<FF> {
+    fn raw_multiply<F>(
+        &self,
+        layouter: &mut impl Layouter<FF>,
+        mut f: F,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        F: FnMut() -> Value<(Assigned<FF>, Assigned<FF>, Assigned<FF>)>,
+    {
+        let config = self.lane();
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                let mut values = None;
+                let lhs = region.assign_advice(
+                    || "lhs",
+                    config.a,
+                    0,
+                    || {
+                        values = Some(f());
+                        values.unwrap().map(|v| v.0)
+                    },
+                )?;
+                let rhs =
+                    region.assign_advice(|| "rhs", config.b, 0, || values.unwrap().map(|v| v.1))?;
+
+                let out =
+                    region.assign_advice(|| "out", config.c, 0, || values.unwrap().map(|v| v.2))?;
+
+                region.assign_fixed(|| "a", config.sa, 0, || Value::known(FF::zero()))?;
+                region.assign_fixed(|| "b", config.sb, 0, || Value::known(FF::zero()))?;
+                region.assign_fixed(|| "c", config.sc, 0, || Value::known(FF::one()))?;
+                region.assign_fixed(|| "a * b", config.sm, 0, || Value::known(FF::one()))?;
+
+                Ok((lhs.cell(), rhs.cell(), out.cell()))
+            },
+        )
+    }
+
+    fn raw_add<F>(
+        &self,
+        layouter: &mut impl Layouter<FF>,
+        mut f: F,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        F: FnMut() -> Value<(Assigned<FF>, Assigned<FF>, Assigned<FF>)>,
+    {
+        let config = self.lane();
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                let mut values = None;
+                let lhs = region.assign_advice(
+                    || "lhs",
+                    config.a,
+                    0,
+                    || {
+                        values = Some(f());
+                        values.unwrap().map(|v| v.0)
+                    },
+                )?;
+                let rhs =
+                    region.assign_advice(|| "rhs", config.b, 0, || values.unwrap().map(|v| v.1))?;
+
+                let out =
+                    region.assign_advice(|| "out", config.c, 0, || values.unwrap().map(|v| v.2))?;
+
+                region.assign_fixed(|| "a", config.sa, 0, || Value::known(FF::one()))?;
+                region.assign_fixed(|| "b", config.sb, 0, || Value::known(FF::one()))?;
+                region.assign_fixed(|| "c", config.sc, 0, || Value::known(FF::one()))?;
+                region.assign_fixed(|| "a * b", config.sm, 0, || Value::known(FF::zero()))?;
+
+                Ok((lhs.cell(), rhs.cell(), out.cell()))
+            },
+        )
+    }
+
+    fn copy(&self, layouter: &mut impl Layouter<FF>, left: Cell, right: Cell) -> Result<(), Error> {
+        layouter.assign_region(
+            || "copy",
+            |mut region| {
+                region.constrain_equal(left, right)?;
+                region.constrain_equal(left, right)
+            },
+        )
+    }
+}
@@ -0,0 +1,294 @@
+//! Transcripts proofs can be created with, besides the Blake2b one of `halo2_proofs`.
+//!
+//! The transcript derives the verifier's challenges from the proof, so its hash decides where a
+//! proof can be verified cheaply: Keccak256 has an EVM precompile, for on-chain verification,
+//! and Poseidon is cheap to compute in a circuit, for recursive verification. Every transcript
+//! writes the same bytes (compressed points and scalars), only the challenges differ.
+//!
+//! The `poseidon` crate is built on the fields of halo2curves 0.3.2 (ff 0.13) rather than on those
+//! of `halo2_proofs` (ff 0.12), so the Poseidon transcript moves every element between the two
+//! through its little endian encoding, which both share.
+
+use ff::PrimeField;
+use group::GroupEncoding;
+use halo2_proofs::{
+    arithmetic::CurveAffine,
+    halo2curves::bn256::G1Affine,
+    transcript::{
+        Challenge255, EncodedChallenge, Transcript, TranscriptRead, TranscriptReadBuffer,
+        TranscriptWrite, TranscriptWriterBuffer,
+    },
+};
+use halo2curves_poseidon::{
+    bn256::Fq,
+    group::ff::{FromUniformBytes, PrimeField as _},
+};
+use poseidon::Poseidon;
+use sha3::{Digest, Keccak256};
+use std::{
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+
+/// Hash function a transcript derives challenges with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TranscriptHash {
+    /// `Blake2bWrite`/`Blake2bRead` of `halo2_proofs`
+    Blake2b,
+    /// [`Keccak256Write`]/[`Keccak256Read`]
+    Keccak256,
+    /// [`PoseidonWrite`]/[`PoseidonRead`]
+    Poseidon,
+}
+
+impl TranscriptHash {
+    pub const ALL: [TranscriptHash; 3] = [
+        TranscriptHash::Blake2b,
+        TranscriptHash::Keccak256,
+        TranscriptHash::Poseidon,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TranscriptHash::Blake2b => "Blake2b",
+            TranscriptHash::Keccak256 => "Keccak256",
+            TranscriptHash::Poseidon => "Poseidon",
+        }
+    }
+
+    /// Parses a name as returned by [`TranscriptHash::name`], ignoring case
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|hash| hash.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// State a transcript absorbs the proof into and squeezes challenges out of
+pub trait Sponge<C: CurveAffine>: Default {
+    fn absorb_point(&mut self, point: C) -> io::Result<()>;
+    fn absorb_scalar(&mut self, scalar: C::Scalar);
+    fn squeeze(&mut self) -> Challenge255<C>;
+}
+
+/// Affine coordinates of `point`, which can't be the point at infinity
+fn coordinates<C: CurveAffine>(point: C) -> io::Result<(C::Base, C::Base)> {
+    let coordinates = Option::from(point.coordinates()).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            "cannot write points at infinity to the transcript",
+        )
+    })?;
+    Ok((*coordinates.x(), *coordinates.y()))
+}
+
+/// `bytes`, at most 64 of them, zero padded to the input of [`Challenge255::new`]
+fn wide(bytes: &[u8]) -> [u8; 64] {
+    let mut wide = [0u8; 64];
+    wide[..bytes.len()].copy_from_slice(bytes);
+    wide
+}
+
+const KECCAK256_PREFIX_CHALLENGE: u8 = 0;
+const KECCAK256_PREFIX_POINT: u8 = 1;
+const KECCAK256_PREFIX_SCALAR: u8 = 2;
+
+/// Keccak256 over the prefixed encodings of what is absorbed, in the same way as the Blake2b
+/// transcript of `halo2_proofs`
+#[derive(Clone, Debug, Default)]
+pub struct Keccak256Sponge {
+    state: Keccak256,
+}
+
+impl<C: CurveAffine> Sponge<C> for Keccak256Sponge {
+    fn absorb_point(&mut self, point: C) -> io::Result<()> {
+        let (x, y) = coordinates(point)?;
+        self.state.update([KECCAK256_PREFIX_POINT]);
+        self.state.update(x.to_repr().as_ref());
+        self.state.update(y.to_repr().as_ref());
+        Ok(())
+    }
+
+    fn absorb_scalar(&mut self, scalar: C::Scalar) {
+        self.state.update([KECCAK256_PREFIX_SCALAR]);
+        self.state.update(scalar.to_repr().as_ref());
+    }
+
+    fn squeeze(&mut self) -> Challenge255<C> {
+        self.state.update([KECCAK256_PREFIX_CHALLENGE]);
+        let hash = self.state.clone().finalize();
+        Challenge255::new(&wide(hash.as_slice()))
+    }
+}
+
+/// Width of the Poseidon permutation, in field elements
+pub const POSEIDON_T: usize = 5;
+/// Number of field elements absorbed per permutation
+pub const POSEIDON_RATE: usize = 4;
+/// Number of full rounds of the Poseidon permutation
+pub const POSEIDON_R_F: usize = 8;
+/// Number of partial rounds of the Poseidon permutation
+pub const POSEIDON_R_P: usize = 60;
+
+/// Poseidon over `F`, which must be the base field of the curve (the native field of a circuit
+/// verifying the proof) as defined by halo2curves 0.3.2. Points are absorbed as their two
+/// coordinates and scalars reduced into the base field.
+pub struct PoseidonSponge<F: FromUniformBytes<64>> {
+    state: Poseidon<F, POSEIDON_T, POSEIDON_RATE>,
+}
+
+impl<F: FromUniformBytes<64>> Default for PoseidonSponge<F> {
+    fn default() -> Self {
+        Self {
+            state: Poseidon::new(POSEIDON_R_F, POSEIDON_R_P),
+        }
+    }
+}
+
+/// The element of `F` encoded by `bytes`, reduced if it doesn't fit
+fn poseidon_element<F: FromUniformBytes<64>>(bytes: &[u8]) -> F {
+    F::from_uniform_bytes(&wide(bytes))
+}
+
+impl<C: CurveAffine, F: FromUniformBytes<64>> Sponge<C> for PoseidonSponge<F> {
+    fn absorb_point(&mut self, point: C) -> io::Result<()> {
+        let (x, y) = coordinates(point)?;
+        self.state.update(&[
+            poseidon_element(x.to_repr().as_ref()),
+            poseidon_element(y.to_repr().as_ref()),
+        ]);
+        Ok(())
+    }
+
+    fn absorb_scalar(&mut self, scalar: C::Scalar) {
+        self.state
+            .update(&[poseidon_element(scalar.to_repr().as_ref())]);
+    }
+
+    fn squeeze(&mut self) -> Challenge255<C> {
+        let challenge = self.state.squeeze();
+        Challenge255::new(&wide(challenge.to_repr().as_ref()))
+    }
+}
+
+/// Transcript writing a proof to `W`, with challenges squeezed out of `S`
+#[derive(Debug)]
+pub struct SpongeWrite<W: Write, C: CurveAffine, S: Sponge<C>> {
+    sponge: S,
+    writer: W,
+    _marker: PhantomData<C>,
+}
+
+/// Transcript reading a proof written by [`SpongeWrite`] from `R`
+#[derive(Debug)]
+pub struct SpongeRead<R: Read, C: CurveAffine, S: Sponge<C>> {
+    sponge: S,
+    reader: R,
+    _marker: PhantomData<C>,
+}
+
+pub type Keccak256Write<W, C> = SpongeWrite<W, C, Keccak256Sponge>;
+pub type Keccak256Read<R, C> = SpongeRead<R, C, Keccak256Sponge>;
+pub type PoseidonWrite<W> = SpongeWrite<W, G1Affine, PoseidonSponge<Fq>>;
+pub type PoseidonRead<R> = SpongeRead<R, G1Affine, PoseidonSponge<Fq>>;
+
+impl<W: Write, C: CurveAffine, S: Sponge<C>> Transcript<C, Challenge255<C>>
+    for SpongeWrite<W, C, S>
+{
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        self.sponge.squeeze()
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        self.sponge.absorb_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.sponge.absorb_scalar(scalar);
+        Ok(())
+    }
+}
+
+impl<W: Write, C: CurveAffine, S: Sponge<C>> TranscriptWrite<C, Challenge255<C>>
+    for SpongeWrite<W, C, S>
+{
+    fn write_point(&mut self, point: C) -> io::Result<()> {
+        self.common_point(point)?;
+        self.writer.write_all(point.to_bytes().as_ref())
+    }
+
+    fn write_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.common_scalar(scalar)?;
+        self.writer.write_all(scalar.to_repr().as_ref())
+    }
+}
+
+impl<W: Write, C: CurveAffine, S: Sponge<C>> TranscriptWriterBuffer<W, C, Challenge255<C>>
+    for SpongeWrite<W, C, S>
+{
+    fn init(writer: W) -> Self {
+        Self {
+            sponge: S::default(),
+            writer,
+            _marker: PhantomData,
+        }
+    }
+
+    fn finalize(self) -> W {
+        self.writer
+    }
+}
+
+impl<R: Read, C: CurveAffine, S: Sponge<C>> Transcript<C, Challenge255<C>> for SpongeRead<R, C, S> {
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        self.sponge.squeeze()
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        self.sponge.absorb_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.sponge.absorb_scalar(scalar);
+        Ok(())
+    }
+}
+
+impl<R: Read, C: CurveAffine, S: Sponge<C>> TranscriptRead<C, Challenge255<C>>
+    for SpongeRead<R, C, S>
+{
+    fn read_point(&mut self) -> io::Result<C> {
+        let mut compressed = <C as GroupEncoding>::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        let point: C = Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Other, "invalid point encoding in proof")
+        })?;
+        self.common_point(point)?;
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        self.common_scalar(scalar)?;
+        Ok(scalar)
+    }
+}
+
+impl<R: Read, C: CurveAffine, S: Sponge<C>> TranscriptReadBuffer<R, C, Challenge255<C>>
+    for SpongeRead<R, C, S>
+{
+    fn init(reader: R) -> Self {
+        Self {
+            sponge: S::default(),
+            reader,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use halo2_proofs::circuit::Value;
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Cell, Chip, Layouter, SimpleFloorPlanner},
+    plonk::{
+        Advice, Assigned, Circuit, Column, ConstraintSystem, Error, Fixed, FloorPlanner, Instance,
+    },
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone)]
+pub struct TutorialConfig {
+    pub l: Column<Advice>,
+    pub r: Column<Advice>,
+    pub o: Column<Advice>,
+
+    pub sl: Column<Fixed>,
+    pub sr: Column<Fixed>,
+    pub so: Column<Fixed>,
+    pub sm: Column<Fixed>,
+    pub sc: Column<Fixed>,
+    pub PI: Column<Instance>,
+}
+
+pub struct TutorialChip<F: FieldExt> {
+    config: TutorialConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> TutorialChip<F> {
+    pub fn new(config: TutorialConfig) -> Self {
+        TutorialChip {
+            config,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> Chip<F> for TutorialChip<F> {
+    type Config = TutorialConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+pub trait TutorialComposer<F: FieldExt> {
+    /// Assigns `(l, r, o)` to a new row constrained by `l * r = o`
+    fn raw_multiply<FM>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        f: FM,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        FM: FnMut() -> Value<(Assigned<F>, Assigned<F>, Assigned<F>)>;
+
+    /// Assigns `(l, r, o)` to a new row constrained by `l + r = o`
+    fn raw_add<FM>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        f: FM,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        FM: FnMut() -> Value<(Assigned<F>, Assigned<F>, Assigned<F>)>;
+
+    /// Ensure two wire values are the same, in effect connecting the wires to each other
+    fn copy(&self, layouter: &mut impl Layouter<F>, a: Cell, b: Cell) -> Result<(), Error>;
+
+    /// Exposes a number as a public input to the circuit.
+    fn expose_public(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        cell: Cell,
+        row: usize,
+    ) -> Result<(), Error>;
+}
+
+impl<F: FieldExt> TutorialComposer<F> for TutorialChip<F> {
+    fn raw_multiply<FM>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        mut f: FM,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        FM: FnMut() -> Value<(Assigned<F>, Assigned<F>, Assigned<F>)>,
+    {
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                let mut values = None;
+                let lhs = region.assign_advice(
+                    || "lhs",
+                    self.config.l,
+                    0,
+                    || {
+                        values = Some(f());
+                        values.unwrap().map(|v| v.0)
+                    },
+                )?;
+                let rhs = region.assign_advice(
+                    || "rhs",
+                    self.config.r,
+                    0,
+                    || values.unwrap().map(|v| v.1),
+                )?;
+
+                let out = region.assign_advice(
+                    || "out",
+                    self.config.o,
+                    0,
+                    || values.unwrap().map(|v| v.2),
+                )?;
+
+                region.assign_fixed(|| "m", self.config.sm, 0, || Value::known(F::one()))?;
+                region.assign_fixed(|| "o", self.config.so, 0, || Value::known(F::one()))?;
+
+                Ok((lhs.cell(), rhs.cell(), out.cell()))
+            },
+        )
+    }
+
+    fn raw_add<FM>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        mut f: FM,
+    ) -> Result<(Cell, Cell, Cell), Error>
+    where
+        FM: FnMut() -> Value<(Assigned<F>, Assigned<F>, Assigned<F>)>,
+    {
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                let mut values = None;
+                let lhs = region.assign_advice(
+                    || "lhs",
+                    self.config.l,
+                    0,
+                    || {
+                        values = Some(f());
+                        values.unwrap().map(|v| v.0)
+                    },
+                )?;
+                let rhs = region.assign_advice(
+                    || "rhs",
+                    self.config.r,
+                    0,
+                    || values.unwrap().map(|v| v.1),
+                )?;
+
+                let out = region.assign_advice(
+                    || "out",
+                    self.config.o,
+                    0,
+                    || values.unwrap().map(|v| v.2),
+                )?;
+
+                region.assign_fixed(|| "l", self.config.sl, 0, || Value::known(F::one()))?;
+                region.assign_fixed(|| "r", self.config.sr, 0, || Value::known(F::one()))?;
+                region.assign_fixed(|| "o", self.config.so, 0, || Value::known(F::one()))?;
+
+                Ok((lhs.cell(), rhs.cell(), out.cell()))
+            },
+        )
+    }
+
+    fn copy(&self, layouter: &mut impl Layouter<F>, left: Cell, right: Cell) -> Result<(), Error> {
+        layouter.assign_region(
+            || "copy",
+            |mut region| {
+                region.constrain_equal(left, right)?;
+                region.constrain_equal(left, right)
+            },
+        )
+    }
+
+    fn expose_public(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        cell: Cell,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell, self.config.PI, row)
+    }
+}
+
+/// Circuit proving knowledge of `x` and `y` such that `x^2 * y^2 + constant` equals the second
+/// public input, where the first public input is `constant`. It is laid out by the floor planner
+/// `P`.
+pub struct TutorialCircuit<F: FieldExt, P: FloorPlanner = SimpleFloorPlanner> {
+    pub x: Value<F>,
+    pub y: Value<F>,
+    pub constant: F,
+    _planner: PhantomData<P>,
+}
+
+impl<F: FieldExt> TutorialCircuit<F> {
+    pub fn new(x: Value<F>, y: Value<F>, constant: F) -> Self {
+        Self {
+            x,
+            y,
+            constant,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> TutorialCircuit<F, P> {
+    /// The same circuit laid out by the floor planner `Q`
+    pub fn with_floor_planner<Q: FloorPlanner>(self) -> TutorialCircuit<F, Q> {
+        TutorialCircuit {
+            x: self.x,
+            y: self.y,
+            constant: self.constant,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Default for TutorialCircuit<F, P> {
+    fn default() -> Self {
+        Self {
+            x: Value::default(),
+            y: Value::default(),
+            constant: F::default(),
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, P: FloorPlanner> Circuit<F> for TutorialCircuit<F, P> {
+    type Config = TutorialConfig;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let l = meta.advice_column();
+        let r = meta.advice_column();
+        let o = meta.advice_column();
+
+        meta.enable_equality(l);
+        meta.enable_equality(r);
+        meta.enable_equality(o);
+
+        let sm = meta.fixed_column();
+        let sl = meta.fixed_column();
+        let sr = meta.fixed_column();
+        let so = meta.fixed_column();
+        let sc = meta.fixed_column();
+        #[allow(non_snake_case)]
+        let PI = meta.instance_column();
+        meta.enable_equality(PI);
+
+        meta.create_gate("mini plonk", |meta| {
+            let l = meta.query_advice(l, Rotation::cur());
+            let r = meta.query_advice(r, Rotation::cur());
+            let o = meta.query_advice(o, Rotation::cur());
+
+            let sl = meta.query_fixed(sl, Rotation::cur());
+            let sr = meta.query_fixed(sr, Rotation::cur());
+            let so = meta.query_fixed(so, Rotation::cur());
+            let sm = meta.query_fixed(sm, Rotation::cur());
+            let sc = meta.query_fixed(sc, Rotation::cur());
+
+            vec![l.clone() * sl + r.clone() * sr + l * r * sm + (o * so * (-F::one())) + sc]
+        });
+
+        TutorialConfig {
+            l,
+            r,
+            o,
+            sl,
+            sr,
+            so,
+            sm,
+            sc,
+            PI,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = TutorialChip::new(config);
+
+        // Initialise these values so that we can access them more easily outside the block we actually give them a value in
+        let x: Value<Assigned<_>> = self.x.into();
+        let y: Value<Assigned<_>> = self.y.into();
+        let consty = Assigned::from(self.constant);
+
+        // Create x squared
+        // Note that the variables named ai for some i are just place holders, meaning that a0 isn't
+        // necessarily the first entry in the column a; though in the code we try to make things clear
+        let (a0, b0, c0) = cs.raw_multiply(&mut layouter, || x.map(|x| (x, x, x * x)))?;
+        cs.copy(&mut layouter, a0, b0)?;
+
+        // Create y squared
+        let (a1, b1, c1) = cs.raw_multiply(&mut layouter, || y.map(|y| (y, y, y * y)))?;
+        cs.copy(&mut layouter, a1, b1)?;
+
+        // Create xy squared
+        let (a2, b2, c2) = cs.raw_multiply(&mut layouter, || {
+            x.zip(y).map(|(x, y)| (x * x, y * y, x * x * y * y))
+        })?;
+        cs.copy(&mut layouter, c0, a2)?;
+        cs.copy(&mut layouter, c1, b2)?;
+
+        // Add the constant
+        let (a3, b3, c3) = cs.raw_add(&mut layouter, || {
+            x.zip(y)
+                .map(|(x, y)| (x * x * y * y, consty, x * x * y * y + consty))
+        })?;
+        cs.copy(&mut layouter, c2, a3)?;
+
+        // Ensure that the constant in the TutorialCircuit struct is correctly used and that the
+        // result of the circuit computation is what is expected. (use expose_public))
+        cs.expose_public(&mut layouter, b3, 0)?;
+        // Below is another way to expose a public value, this time the output value of the computation
+        // (Use constrain_instance)
+        layouter.constrain_instance(c3, cs.config.PI, 1)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use halo2::{
+    cache::{constraint_system_hash, has_selectors, params_hash, synthesis_hash, KeyCache},
+    iszero::IsZeroCircuit,
+    iszero_chip::IsZeroChipCircuit,
+    prover::{prove, verify},
+    tutorial::TutorialCircuit,
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
+use std::{env, fs, process};
+
+fn tutorial_circuit() -> TutorialCircuit<Fr> {
+    TutorialCircuit::new(
+        Value::known(Fr::from(5)),
+        Value::known(Fr::from(9)),
+        Fr::from(7),
+    )
+}
+
+#[test]
+fn test_cache_reloads_params_and_keys() {
+    let dir = env::temp_dir().join(format!("halo2-cache-{}", process::id()));
+    let cache = KeyCache::new(&dir);
+    let k = 4;
+
+    let params = cache.params(k);
+    assert!(dir.join("params-4.bin").exists());
+    assert_eq!(params_hash(&cache.params(k)), params_hash(&params));
+
+    let vk = cache.vk("Tutorial", &params, &tutorial_circuit()).unwrap();
+    let entries = fs::read_dir(&dir).unwrap().count();
+    let reloaded = cache.vk("Tutorial", &params, &tutorial_circuit()).unwrap();
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), entries);
+    assert_eq!(
+        format!("{:?}", reloaded.pinned()),
+        format!("{:?}", vk.pinned())
+    );
+
+    // The proving key built from the cached verifying key proves
+    let pk = cache.pk("Tutorial", &params, &tutorial_circuit()).unwrap();
+    let instances: &[&[&[Fr]]] = &[&[&[Fr::from(7), Fr::from(25 * 81 + 7)]]];
+    let proof = prove(&params, &pk, &[tutorial_circuit()], instances).unwrap();
+    verify(&params, pk.get_vk(), instances, &proof).expect("proof verification should not fail");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cache_regenerates_keys_with_selectors() {
+    let dir = env::temp_dir().join(format!("halo2-cache-selectors-{}", process::id()));
+    let cache = KeyCache::new(&dir);
+    let k = 6;
+    let circuit = || IsZeroChipCircuit::new(Value::known(Fr::from(2)), 10);
+    assert!(has_selectors::<Fr, IsZeroChipCircuit<Fr>>());
+    assert!(!has_selectors::<Fr, TutorialCircuit<Fr>>());
+
+    let params = cache.params(k);
+    let entries = fs::read_dir(&dir).unwrap().count();
+    let vk = cache.vk("IsZero Chip", &params, &circuit()).unwrap();
+    let reloaded = cache.vk("IsZero Chip", &params, &circuit()).unwrap();
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), entries);
+    assert_eq!(
+        format!("{:?}", reloaded.pinned()),
+        format!("{:?}", vk.pinned())
+    );
+
+    // The key of the second call proves and verifies
+    let pk = cache.pk("IsZero Chip", &params, &circuit()).unwrap();
+    let instances: &[&[&[Fr]]] = &[&[]];
+    let proof = prove(&params, &pk, &[circuit()], instances).unwrap();
+    verify(&params, pk.get_vk(), instances, &proof).expect("proof verification should not fail");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_constraint_system_hash() {
+    assert_eq!(
+        constraint_system_hash::<TutorialCircuit<Fr>>(),
+        constraint_system_hash::<TutorialCircuit<Fr>>()
+    );
+    assert_ne!(
+        constraint_system_hash::<TutorialCircuit<Fr>>(),
+        constraint_system_hash::<IsZeroCircuit<Fr>>()
+    );
+}
+
+#[test]
+fn test_synthesis_hash() {
+    // Neither the witness nor the public inputs are committed to by the verifying key
+    let other_inputs = TutorialCircuit::new(
+        Value::known(Fr::from(2)),
+        Value::known(Fr::from(3)),
+        Fr::from(8),
+    );
+    assert_eq!(
+        synthesis_hash(&tutorial_circuit()),
+        synthesis_hash(&other_inputs)
+    );
+
+    // The repeats of IsZero share a constraint system but not their selectors
+    let repeats = |repeats| IsZeroCircuit::new(Value::known(Fr::from(2)), repeats);
+    assert_eq!(synthesis_hash(&repeats(2)), synthesis_hash(&repeats(2)));
+    assert_ne!(synthesis_hash(&repeats(1)), synthesis_hash(&repeats(2)));
+}
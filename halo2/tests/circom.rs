@@ -0,0 +1,194 @@
+use ff::{Field, PrimeField};
+use halo2::{
+    circom::{R1csFile, WtnsFile},
+    groth16::circuits::iszero,
+    r1cs::{check_r1cs, LinearCombination, R1cs, R1csFailure, WireValue},
+};
+use pairing::bn256::Fr;
+use std::io::Cursor;
+
+/// Modulus of `Fr` as a little endian integer, i.e. `-1 + 1`
+fn modulus() -> Vec<u8> {
+    let mut bytes = (-Fr::one()).to_repr().as_ref().to_vec();
+    for byte in bytes.iter_mut() {
+        let (value, carry) = byte.overflowing_add(1);
+        *byte = value;
+        if !carry {
+            break;
+        }
+    }
+    bytes
+}
+
+fn section(file: &mut Vec<u8>, section_type: u32, content: Vec<u8>) {
+    file.extend_from_slice(&section_type.to_le_bytes());
+    file.extend_from_slice(&(content.len() as u64).to_le_bytes());
+    file.extend(content);
+}
+
+fn write_linear_combination(content: &mut Vec<u8>, lc: &LinearCombination<Fr>) {
+    content.extend_from_slice(&(lc.terms.len() as u32).to_le_bytes());
+    for (wire, coefficient) in lc.terms.iter() {
+        content.extend_from_slice(&(*wire as u32).to_le_bytes());
+        content.extend_from_slice(coefficient.to_repr().as_ref());
+    }
+}
+
+/// Writes `r1cs` in the `.r1cs` format, with `num_pub_out` of its public wires as outputs and the
+/// constraints section before the header, which readers should accept
+fn write_r1cs(
+    r1cs: &R1cs<Fr>,
+    num_pub_out: u32,
+    custom_gates: Option<(Vec<u8>, Vec<u8>)>,
+) -> Vec<u8> {
+    let mut file = b"r1cs".to_vec();
+    file.extend_from_slice(&1u32.to_le_bytes());
+    let num_sections: u32 = if custom_gates.is_some() { 5 } else { 3 };
+    file.extend_from_slice(&num_sections.to_le_bytes());
+
+    let mut content = vec![];
+    for constraint in r1cs.constraints.iter() {
+        write_linear_combination(&mut content, &constraint.a);
+        write_linear_combination(&mut content, &constraint.b);
+        write_linear_combination(&mut content, &constraint.c);
+    }
+    section(&mut file, 2, content);
+
+    let mut content = vec![];
+    content.extend_from_slice(&32u32.to_le_bytes());
+    content.extend(modulus());
+    for value in [
+        r1cs.num_wires as u32,
+        num_pub_out,
+        r1cs.num_public as u32 - num_pub_out,
+        0,
+    ] {
+        content.extend_from_slice(&value.to_le_bytes());
+    }
+    content.extend_from_slice(&(r1cs.num_wires as u64).to_le_bytes());
+    content.extend_from_slice(&(r1cs.constraints.len() as u32).to_le_bytes());
+    section(&mut file, 1, content);
+
+    let content = r1cs
+        .wire_labels
+        .iter()
+        .flat_map(|label| label.to_le_bytes())
+        .collect();
+    section(&mut file, 3, content);
+
+    if let Some((list, application)) = custom_gates {
+        section(&mut file, 4, list);
+        section(&mut file, 5, application);
+    }
+    file
+}
+
+/// Writes `witness` in the `.wtns` format
+fn write_wtns(witness: &[Fr]) -> Vec<u8> {
+    let mut file = b"wtns".to_vec();
+    file.extend_from_slice(&2u32.to_le_bytes());
+    file.extend_from_slice(&2u32.to_le_bytes());
+
+    let mut content = vec![];
+    content.extend_from_slice(&32u32.to_le_bytes());
+    content.extend(modulus());
+    content.extend_from_slice(&(witness.len() as u32).to_le_bytes());
+    section(&mut file, 1, content);
+
+    let content = witness
+        .iter()
+        .flat_map(|value| value.to_repr().as_ref().to_vec())
+        .collect();
+    section(&mut file, 2, content);
+    file
+}
+
+#[test]
+fn test_read_r1cs() {
+    let (r1cs, _) = iszero(Fr::zero(), 4);
+    let file = R1csFile::<Fr>::read(Cursor::new(write_r1cs(&r1cs, 4, None))).unwrap();
+
+    assert_eq!(file.version, 1);
+    assert_eq!(file.header.num_pub_out, 4);
+    assert_eq!(file.header.num_pub_in, 0);
+    assert_eq!(file.header.num_constraints, 8);
+    assert_eq!(file.num_constraints(), 8);
+    assert_eq!(file.num_wires(), r1cs.num_wires);
+    assert_eq!(file.r1cs, r1cs);
+    assert!(file.custom_gates.is_empty());
+}
+
+#[test]
+fn test_read_r1cs_custom_gates() {
+    let (r1cs, _) = iszero(Fr::zero(), 1);
+
+    let mut list = 1u32.to_le_bytes().to_vec();
+    list.extend_from_slice(b"Example\0");
+    list.extend_from_slice(&1u32.to_le_bytes());
+    list.extend_from_slice(Fr::from(3).to_repr().as_ref());
+    let mut application = 1u32.to_le_bytes().to_vec();
+    application.extend_from_slice(&0u32.to_le_bytes());
+    application.extend_from_slice(&2u32.to_le_bytes());
+    application.extend_from_slice(&1u64.to_le_bytes());
+    application.extend_from_slice(&2u64.to_le_bytes());
+
+    let bytes = write_r1cs(&r1cs, 1, Some((list, application)));
+    let file = R1csFile::<Fr>::read(Cursor::new(bytes)).unwrap();
+    assert_eq!(file.custom_gates.len(), 1);
+    assert_eq!(file.custom_gates[0].name, "Example");
+    assert_eq!(file.custom_gates[0].parameters, vec![Fr::from(3)]);
+    assert_eq!(file.custom_gate_uses.len(), 1);
+    assert_eq!(file.custom_gate_uses[0].gate, 0);
+    assert_eq!(file.custom_gate_uses[0].signals, vec![1, 2]);
+}
+
+#[test]
+fn test_read_r1cs_rejects_other_files() {
+    let (r1cs, _) = iszero(Fr::zero(), 1);
+    let mut bytes = write_r1cs(&r1cs, 1, None);
+    bytes[..4].copy_from_slice(b"wtns");
+    assert!(R1csFile::<Fr>::read(Cursor::new(bytes)).is_err());
+
+    // Truncated constraints section
+    let bytes = write_r1cs(&r1cs, 1, None);
+    assert!(R1csFile::<Fr>::read(Cursor::new(bytes[..40].to_vec())).is_err());
+}
+
+#[test]
+fn test_read_wtns() {
+    let (r1cs, witness) = iszero(Fr::from(2), 3);
+    let file = WtnsFile::<Fr>::read(Cursor::new(write_wtns(&witness))).unwrap();
+    assert_eq!(file.version, 2);
+    assert_eq!(file.witness, witness);
+    assert_eq!(check_r1cs(&r1cs, &file.witness), Ok(()));
+}
+
+#[test]
+fn test_check_r1cs_failures() {
+    let (r1cs, mut witness) = iszero(Fr::zero(), 2);
+    assert_eq!(
+        check_r1cs(&r1cs, &witness[1..]),
+        Err(R1csFailure::WitnessLength {
+            expected: 6,
+            actual: 5
+        })
+    );
+
+    // Claim that the first output is zero: `in * inv = 1 - out` fails for the first repeat
+    witness[1] = Fr::zero();
+    let wire = |wire: usize| WireValue {
+        wire,
+        label: wire as u64,
+        value: witness[wire],
+    };
+    assert_eq!(
+        check_r1cs(&r1cs, &witness),
+        Err(R1csFailure::ConstraintNotSatisfied {
+            constraint: 0,
+            a: Fr::zero(),
+            b: Fr::zero(),
+            c: Fr::one(),
+            wires: vec![wire(3), wire(4), wire(0), wire(1)],
+        })
+    );
+}
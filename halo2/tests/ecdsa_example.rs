@@ -1,248 +1,34 @@
-// Note that all the code here was copied from the original repository for the purposes
+// Note that the circuit tested here was copied from the original repository for the purposes
 // of placing it with other tutorial/example code in this repo.
 
+use halo2::ecdsa::EcdsaVerifyCircuit;
 use halo2wrong::{
     curves::{
         bn256::Fr as BnScalar,
         pasta::{Fp as PastaFp, Fq as PastaFq},
         secp256k1::Secp256k1Affine as Secp256k1,
     },
-    halo2::{
-        arithmetic::{CurveAffine, FieldExt},
-        circuit::{Layouter, SimpleFloorPlanner, Value},
-        plonk::*,
-    },
-};
-use rand_core::OsRng;
-
-use ecc::{integer::Range, EccConfig, GeneralEccChip};
-use ecdsa::ecdsa::{AssignedEcdsaSig, AssignedPublicKey, EcdsaChip};
-use group::{
-    ff::{Field, PrimeField},
-    Curve, Group,
+    halo2::arithmetic::{CurveAffine, FieldExt},
 };
-use integer::IntegerInstructions;
-use maingate::{
-    big_to_fe, fe_to_big, mock_prover_verify, MainGate, MainGateConfig, RangeChip, RangeConfig,
-    RangeInstructions, RegionCtx,
-};
-use std::marker::PhantomData;
-
-const BIT_LEN_LIMB: usize = 68;
-const NUMBER_OF_LIMBS: usize = 4;
-
-#[derive(Clone, Debug)]
-struct TestCircuitEcdsaVerifyConfig {
-    main_gate_config: MainGateConfig,
-    range_config: RangeConfig,
-}
-
-impl TestCircuitEcdsaVerifyConfig {
-    pub fn new<C: CurveAffine, N: FieldExt>(meta: &mut ConstraintSystem<N>) -> Self {
-        let (rns_base, rns_scalar) = GeneralEccChip::<C, N, NUMBER_OF_LIMBS, BIT_LEN_LIMB>::rns();
-        let main_gate_config = MainGate::<N>::configure(meta);
-        let mut overflow_bit_lens: Vec<usize> = vec![];
-        overflow_bit_lens.extend(rns_base.overflow_lengths());
-        overflow_bit_lens.extend(rns_scalar.overflow_lengths());
-        let composition_bit_lens = vec![BIT_LEN_LIMB / NUMBER_OF_LIMBS];
-
-        let range_config = RangeChip::<N>::configure(
-            meta,
-            &main_gate_config,
-            composition_bit_lens,
-            overflow_bit_lens,
-        );
-        TestCircuitEcdsaVerifyConfig {
-            main_gate_config,
-            range_config,
-        }
-    }
-
-    pub fn ecc_chip_config(&self) -> EccConfig {
-        EccConfig::new(self.range_config.clone(), self.main_gate_config.clone())
-    }
-
-    pub fn config_range<N: FieldExt>(&self, layouter: &mut impl Layouter<N>) -> Result<(), Error> {
-        let range_chip = RangeChip::<N>::new(self.range_config.clone());
-        range_chip.load_table(layouter)?;
-
-        Ok(())
-    }
-}
-
-#[derive(Default, Clone)]
-struct TestCircuitEcdsaVerify<E: CurveAffine, N: FieldExt> {
-    public_key: Value<E>,
-    signature: Value<(E::Scalar, E::Scalar)>,
-    msg_hash: Value<E::Scalar>,
-
-    aux_generator: E,
-    window_size: usize,
-    _marker: PhantomData<N>,
-}
-
-impl<E: CurveAffine, N: FieldExt> Circuit<N> for TestCircuitEcdsaVerify<E, N> {
-    type Config = TestCircuitEcdsaVerifyConfig;
-    type FloorPlanner = SimpleFloorPlanner;
-
-    fn without_witnesses(&self) -> Self {
-        Self::default()
-    }
-
-    fn configure(meta: &mut ConstraintSystem<N>) -> Self::Config {
-        TestCircuitEcdsaVerifyConfig::new::<E, N>(meta)
-    }
-
-    fn synthesize(
-        &self,
-        config: Self::Config,
-        mut layouter: impl Layouter<N>,
-    ) -> Result<(), Error> {
-        let mut ecc_chip =
-            GeneralEccChip::<E, N, NUMBER_OF_LIMBS, BIT_LEN_LIMB>::new(config.ecc_chip_config());
-
-        layouter.assign_region(
-            || "assign aux values",
-            |region| {
-                let offset = 0;
-                let ctx = &mut RegionCtx::new(region, offset);
 
-                ecc_chip.assign_aux_generator(ctx, Value::known(self.aux_generator))?;
-                ecc_chip.assign_aux(ctx, self.window_size, 1)?;
-                Ok(())
-            },
-        )?;
+use group::ff::PrimeField;
+use maingate::mock_prover_verify;
 
-        let ecdsa_chip = EcdsaChip::new(ecc_chip.clone());
-        let scalar_chip = ecc_chip.scalar_field_chip();
-
-        layouter.assign_region(
-            || "region 0",
-            |region| {
-                let offset = 0;
-                let ctx = &mut RegionCtx::new(region, offset);
-
-                let r = self.signature.map(|signature| signature.0);
-                let s = self.signature.map(|signature| signature.1);
-                let integer_r = ecc_chip.new_unassigned_scalar(r);
-                let integer_s = ecc_chip.new_unassigned_scalar(s);
-                let msg_hash = ecc_chip.new_unassigned_scalar(self.msg_hash);
-
-                let r_assigned = scalar_chip.assign_integer(ctx, integer_r, Range::Remainder)?;
-                let s_assigned = scalar_chip.assign_integer(ctx, integer_s, Range::Remainder)?;
-                let sig = AssignedEcdsaSig {
-                    r: r_assigned,
-                    s: s_assigned,
-                };
-
-                let pk_in_circuit = ecc_chip.assign_point(ctx, self.public_key)?;
-                let pk_assigned = AssignedPublicKey {
-                    point: pk_in_circuit.clone(),
-                };
-
-                let msg_hash = scalar_chip.assign_integer(ctx, msg_hash, Range::Remainder)?;
-                ecdsa_chip.verify(ctx, &sig, &pk_assigned, &msg_hash)
-            },
-        )?;
-
-        config.config_range(&mut layouter)?;
-
-        Ok(())
-    }
-}
-
-fn mod_n<C: CurveAffine>(x: C::Base) -> C::Scalar {
-    let x_big = fe_to_big(x);
-    big_to_fe(x_big)
-}
-
-fn run<C: CurveAffine, N: FieldExt>() -> (TestCircuitEcdsaVerify<C, N>, Vec<Vec<N>>) {
-    let g = C::generator();
-
-    // Generate a key pair
-    let sk = <C as CurveAffine>::ScalarExt::random(OsRng);
-    let public_key = (g * sk).to_affine();
-
-    // Generate a valid signature
-    // Suppose `m_hash` is the message hash
-    let msg_hash = <C as CurveAffine>::ScalarExt::random(OsRng);
-
-    // Draw randomness
-    let k = <C as CurveAffine>::ScalarExt::random(OsRng);
-    let k_inv = k.invert().unwrap();
-
-    // Calculate `r`
-    let r_point = (g * k).to_affine().coordinates().unwrap();
-    let x = r_point.x();
-    let r = mod_n::<C>(*x);
-
-    // Calculate `s`
-    let s = k_inv * (msg_hash + (r * sk));
-
-    // Sanity check. Ensure we construct a valid signature. So lets verify it
-    {
-        let s_inv = s.invert().unwrap();
-        let u_1 = msg_hash * s_inv;
-        let u_2 = r * s_inv;
-        let r_point = ((g * u_1) + (public_key * u_2))
-            .to_affine()
-            .coordinates()
-            .unwrap();
-        let x_candidate = r_point.x();
-        let r_candidate = mod_n::<C>(*x_candidate);
-        assert_eq!(r, r_candidate);
-    }
-
-    let aux_generator = C::CurveExt::random(OsRng).to_affine();
-    let circuit = TestCircuitEcdsaVerify::<C, N> {
-        public_key: Value::known(public_key),
-        signature: Value::known((r, s)),
-        msg_hash: Value::known(msg_hash),
-        aux_generator,
-        window_size: 2,
-        ..Default::default()
-    };
+fn run<C: CurveAffine, N: FieldExt>() -> (EcdsaVerifyCircuit<C, N>, Vec<Vec<N>>) {
+    let circuit = EcdsaVerifyCircuit::<C, N>::random();
     let instance = vec![vec![]];
     assert_eq!(mock_prover_verify(&circuit, instance.clone()), Ok(()));
 
     (circuit, instance)
 }
 
-fn run_fixed<C: CurveAffine, N: FieldExt>(
+fn run_fixed<N: FieldExt>(
     sk: <Secp256k1 as CurveAffine>::ScalarExt,
     msg_hash: <Secp256k1 as CurveAffine>::ScalarExt,
     r: <Secp256k1 as CurveAffine>::ScalarExt,
     s: <Secp256k1 as CurveAffine>::ScalarExt,
-) -> (TestCircuitEcdsaVerify<Secp256k1, N>, Vec<Vec<N>>) {
-    // This function always returns the same generator, it is not random
-    let g = Secp256k1::generator();
-
-    // Generate a key pair
-    let public_key = (g * sk).to_affine();
-
-    // Sanity check. Ensure we construct a valid signature. So lets verify it
-    {
-        let s_inv = s.invert().unwrap();
-        let u_1 = msg_hash * s_inv;
-        let u_2 = r * s_inv;
-        let r_point = ((g * u_1) + (public_key * u_2))
-            .to_affine()
-            .coordinates()
-            .unwrap();
-        let x_candidate = r_point.x();
-        let r_candidate = mod_n::<Secp256k1>(*x_candidate);
-        assert_eq!(r, r_candidate);
-    }
-
-    let aux_generator = <Secp256k1 as CurveAffine>::CurveExt::random(OsRng).to_affine();
-    let circuit = TestCircuitEcdsaVerify::<Secp256k1, N> {
-        public_key: Value::known(public_key),
-        signature: Value::known((r, s)),
-        msg_hash: Value::known(msg_hash),
-        aux_generator,
-        window_size: 2,
-        ..Default::default()
-    };
+) -> (EcdsaVerifyCircuit<Secp256k1, N>, Vec<Vec<N>>) {
+    let circuit = EcdsaVerifyCircuit::<Secp256k1, N>::from_signature(sk, msg_hash, r, s);
     let instance = vec![vec![]];
     assert_eq!(mock_prover_verify(&circuit, instance.clone()), Ok(()));
 
@@ -279,5 +65,5 @@ fn test_ecdsa_fixed_example() {
     ])
     .unwrap();
 
-    run_fixed::<Secp256k1, BnScalar>(sk, msg_hash, r, s);
+    run_fixed::<BnScalar>(sk, msg_hash, r, s);
 }
@@ -0,0 +1,40 @@
+use halo2::{
+    fit::{fits, usable_rows},
+    iszero::IsZeroCircuit,
+    range::RangeCircuit,
+};
+use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr as Fp};
+
+#[test]
+fn test_iszero_fills_rows() {
+    let k = 8;
+    let build = |repeats: usize| {
+        (
+            IsZeroCircuit::new(Value::known(Fp::from(1)), repeats),
+            vec![],
+        )
+    };
+
+    // Every repeat takes three rows, so the fitted circuit should leave less than three unused
+    let repeats = IsZeroCircuit::<Fp>::repeats_for_k(k);
+    assert_eq!(repeats, usable_rows::<Fp, IsZeroCircuit<Fp>>(k) / 3);
+    assert!(fits(k, repeats, &build));
+    assert!(!fits(k, repeats + 1, &build));
+}
+
+#[test]
+fn test_range_fills_rows() {
+    let k = 11;
+    let inputs = RangeCircuit::<Fp>::bench_inputs();
+    let build = |repeats: usize| {
+        (
+            RangeCircuit::new(inputs.clone(), repeats as u32),
+            vec![vec![]],
+        )
+    };
+
+    let repeats = RangeCircuit::<Fp>::bench_repeats_for_k(k) as usize;
+    assert!(repeats > 0);
+    assert!(fits(k, repeats, &build));
+    assert!(!fits(k, repeats + 1, &build));
+}
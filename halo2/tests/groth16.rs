@@ -0,0 +1,104 @@
+use ff::Field;
+use halo2::{
+    circom::R1csFile,
+    groth16::{
+        circuits::{
+            iszero, iszero_repeats_for_k, less_than_repeats_for_k, repeated_less_than,
+            LESS_THAN_BITS,
+        },
+        domain_size, prepare_verifying_key, prove, setup, verify, Error,
+    },
+    r1cs::check_r1cs,
+};
+use pairing::bn256::Fr;
+use rand_core::OsRng;
+use std::path::Path;
+
+#[test]
+fn test_iszero_groth16() {
+    for a in [Fr::zero(), Fr::from(2)] {
+        let (r1cs, witness) = iszero(a, 10);
+        assert_eq!(check_r1cs(&r1cs, &witness), Ok(()));
+
+        let pk = setup(&r1cs, OsRng);
+        let pvk = prepare_verifying_key(&pk.vk);
+        let proof = prove(&pk, &r1cs, &witness, OsRng);
+        assert_eq!(proof.to_bytes().len(), 128);
+        assert_eq!(verify(&pvk, r1cs.public_inputs(&witness), &proof), Ok(()));
+    }
+}
+
+#[test]
+fn test_less_than_groth16() {
+    for (a, b) in [(2, 3), (3, 2), (3, 3)] {
+        let (r1cs, witness) = repeated_less_than(a, b, 5, LESS_THAN_BITS);
+        assert_eq!(check_r1cs(&r1cs, &witness), Ok(()));
+        let expected = if a < b { Fr::one() } else { Fr::zero() };
+        assert_eq!(r1cs.public_inputs(&witness), &[expected]);
+
+        let pk = setup(&r1cs, OsRng);
+        // alpha in G1, beta, gamma and delta in G2, then the constant one and the output in G1
+        assert_eq!(pk.vk.to_bytes().len(), 32 + 3 * 64 + 2 * 32);
+        let pvk = prepare_verifying_key(&pk.vk);
+        let proof = prove(&pk, &r1cs, &witness, OsRng);
+        assert_eq!(verify(&pvk, r1cs.public_inputs(&witness), &proof), Ok(()));
+    }
+}
+
+#[test]
+fn test_groth16_wrong_public_inputs() {
+    let (r1cs, witness) = repeated_less_than(2, 3, 5, LESS_THAN_BITS);
+    let pk = setup(&r1cs, OsRng);
+    let pvk = prepare_verifying_key(&pk.vk);
+    let proof = prove(&pk, &r1cs, &witness, OsRng);
+
+    // Claim that 2 < 3 is false
+    assert_eq!(
+        verify(&pvk, &[Fr::zero()], &proof),
+        Err(Error::InvalidProof)
+    );
+    assert_eq!(
+        verify(&pvk, &[], &proof),
+        Err(Error::PublicInputCount {
+            expected: 1,
+            actual: 0
+        })
+    );
+}
+
+#[test]
+fn test_groth16_fills_domain() {
+    let k = 10;
+
+    let repeats = iszero_repeats_for_k(k);
+    assert_eq!(domain_size(&iszero(Fr::one(), repeats).0), 1 << k);
+    assert!(domain_size(&iszero(Fr::one(), repeats + 1).0) > 1 << k);
+
+    let repeats = less_than_repeats_for_k(k, LESS_THAN_BITS);
+    let build = |repeats| repeated_less_than::<Fr>(2, 3, repeats, LESS_THAN_BITS).0;
+    assert_eq!(domain_size(&build(repeats)), 1 << k);
+    assert!(domain_size(&build(repeats + 1)) > 1 << k);
+}
+
+#[test]
+fn test_less_than_constraints() {
+    // As in `range.circom`, n + 1 constraints per repeat, plus the one of the output
+    for repeats in [1, 5] {
+        let (r1cs, _) = repeated_less_than::<Fr>(2, 3, repeats, LESS_THAN_BITS);
+        assert_eq!(r1cs.num_constraints(), (LESS_THAN_BITS + 1) * repeats + 1);
+    }
+}
+
+// Needs the `range.r1cs` compiled by `circom range.circom --r1cs` in `groth16/range`
+#[test]
+#[ignore]
+fn test_less_than_constraints_match_circom() {
+    let path = Path::new("../groth16/range/range.r1cs");
+    let file = match R1csFile::<Fr>::open(path) {
+        Ok(file) => file,
+        Err(e) => panic!("failed to read {}: {}", path.display(), e),
+    };
+    // The repeats of the `main` component of `range.circom`
+    let (r1cs, _) = repeated_less_than::<Fr>(2, 3, 29127, LESS_THAN_BITS);
+    assert_eq!(r1cs.num_constraints(), file.num_constraints());
+}
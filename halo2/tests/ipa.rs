@@ -0,0 +1,42 @@
+use halo2::{
+    ipa::{keygen, params, prove, verify},
+    tutorial::TutorialCircuit,
+};
+use halo2_proofs::{circuit::Value, halo2curves::pasta::Fp};
+
+fn tutorial_circuit() -> TutorialCircuit<Fp> {
+    TutorialCircuit::new(
+        Value::known(Fp::from(5)),
+        Value::known(Fp::from(9)),
+        Fp::from(7),
+    )
+}
+
+#[test]
+fn test_ipa_proof() {
+    let params = params(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances: &[&[&[Fp]]] = &[&[&[Fp::from(7), Fp::from(25 * 81 + 7)]]];
+
+    let proof = prove(&params, &pk, &[tutorial_circuit()], instances)
+        .expect("proof generation should not fail");
+    verify(&params, pk.get_vk(), instances, &proof).expect("proof verification should not fail");
+
+    // The proof doesn't verify against other public inputs
+    let wrong_instances: &[&[&[Fp]]] = &[&[&[Fp::from(7), Fp::from(25 * 81 + 8)]]];
+    assert!(verify(&params, pk.get_vk(), wrong_instances, &proof).is_err());
+}
+
+#[test]
+fn test_ipa_params_are_deterministic() {
+    // No secret goes into IPA parameters, so a verifier can regenerate those of the prover
+    let params = params(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances: &[&[&[Fp]]] = &[&[&[Fp::from(7), Fp::from(25 * 81 + 7)]]];
+    let proof = prove(&params, &pk, &[tutorial_circuit()], instances)
+        .expect("proof generation should not fail");
+
+    let verifier_params = params(4);
+    verify(&verifier_params, pk.get_vk(), instances, &proof)
+        .expect("proof verification should not fail");
+}
@@ -0,0 +1,152 @@
+use halo2::{
+    fit::usable_rows,
+    iszero::{IsZeroCircuit, IsZeroPublicCircuit, IsZeroWideCircuit},
+    iszero_chip::{IsZeroChip, IsZeroChipCircuit, IsZeroConfig, IsZeroInstructions},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    halo2curves::bn256::Fr as Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+#[test]
+fn test_iszero_public_outputs() {
+    let k = 8;
+    for a in [Fp::zero(), Fp::from(2)] {
+        for expose_input in [false, true] {
+            let circuit = IsZeroPublicCircuit::new(Value::known(a), 5, expose_input);
+            let instances = IsZeroPublicCircuit::instances(a, 5, expose_input);
+            assert_eq!(instances[0].len(), if expose_input { 6 } else { 5 });
+            let prover = MockProver::run(k, &circuit, instances).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn test_iszero_public_wrong_output() {
+    let k = 8;
+    let a = Fp::from(2);
+    let circuit = IsZeroPublicCircuit::new(Value::known(a), 5, false);
+    let mut instances = IsZeroPublicCircuit::instances(a, 5, false);
+    instances[0][3] = Fp::one();
+    let prover = MockProver::run(k, &circuit, instances).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_iszero_public_wrong_input() {
+    let k = 8;
+    let a = Fp::from(2);
+    let circuit = IsZeroPublicCircuit::new(Value::known(a), 5, true);
+    let mut instances = IsZeroPublicCircuit::instances(a, 5, true);
+    instances[0][5] = Fp::from(3);
+    let prover = MockProver::run(k, &circuit, instances).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_iszero_public_repeats_for_k() {
+    // The instance column doesn't add rows, the gates still take three per repeat
+    let k = 10;
+    assert_eq!(
+        IsZeroPublicCircuit::<Fp>::repeats_for_k(k, false),
+        IsZeroCircuit::<Fp>::repeats_for_k(k)
+    );
+}
+
+#[test]
+fn test_iszero_chip() {
+    let k = 8;
+    for a in [Fp::zero(), Fp::from(2)] {
+        let circuit = IsZeroChipCircuit::new(Value::known(a), 10);
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
+
+#[test]
+fn test_iszero_chip_fills_rows() {
+    // One row per repeat, against three for the standard PLONK version
+    let k = 10;
+    let repeats = IsZeroChipCircuit::<Fp>::repeats_for_k(k);
+    assert_eq!(repeats, usable_rows::<Fp, IsZeroChipCircuit<Fp>>(k));
+    assert!(repeats > 2 * IsZeroCircuit::<Fp>::repeats_for_k(k));
+}
+
+/// Assigns `value` to the chip, then overwrites the `out` it computed with `out`
+struct WrongOutCircuit {
+    value: Fp,
+    out: Fp,
+}
+
+impl Circuit<Fp> for WrongOutCircuit {
+    type Config = IsZeroConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            value: self.value,
+            out: self.out,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> IsZeroConfig {
+        IsZeroChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: IsZeroConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = IsZeroChip::new(config.clone());
+        chip.is_zero(layouter.namespace(|| "iszero"), Value::known(self.value))?;
+        layouter.assign_region(
+            || "wrong out",
+            |mut region| {
+                config.q_enable.enable(&mut region, 0)?;
+                region.assign_advice(|| "value", config.value, 0, || Value::known(self.value))?;
+                let value_inv = ff::Field::invert(&self.value).unwrap_or(Fp::zero());
+                region.assign_advice(
+                    || "value inv",
+                    config.value_inv,
+                    0,
+                    || Value::known(value_inv),
+                )?;
+                region.assign_advice(|| "out", config.out, 0, || Value::known(self.out))
+            },
+        )?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_iszero_chip_wrong_out() {
+    let k = 4;
+    for (value, out) in [(Fp::zero(), Fp::zero()), (Fp::from(2), Fp::one())] {
+        let prover = MockProver::run(k, &WrongOutCircuit { value, out }, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[test]
+fn test_iszero_wide() {
+    let k = 8;
+    for a in [Fp::zero(), Fp::from(2)] {
+        let circuit = IsZeroWideCircuit::<Fp, 4>::new(Value::known(a), 20);
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
+
+#[test]
+fn test_iszero_wide_fills_rows() {
+    // Width 1 is the layout of `IsZeroCircuit`, and every lane takes gates off the others' rows
+    let k = 10;
+    let repeats = IsZeroCircuit::<Fp>::repeats_for_k(k);
+    assert_eq!(IsZeroWideCircuit::<Fp, 1>::repeats_for_k(k), repeats);
+    assert!(IsZeroWideCircuit::<Fp, 2>::repeats_for_k(k) > 3 * repeats / 2);
+    assert!(IsZeroWideCircuit::<Fp, 4>::repeats_for_k(k) > 3 * repeats);
+}
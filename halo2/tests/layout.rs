@@ -0,0 +1,113 @@
+use halo2::{
+    ecdsa::EcdsaVerifyCircuit,
+    fit::usable_rows,
+    iszero::IsZeroCircuit,
+    iszero_chip::IsZeroChipCircuit,
+    layout::{rows_used, WithFloorPlanner},
+    range::RangeCircuit,
+};
+use halo2_proofs::{
+    circuit::{floor_planner::V1, Value},
+    dev::MockProver,
+    halo2curves::bn256::Fr as Fp,
+    plonk::Circuit,
+};
+use halo2wrong::curves::secp256k1::Secp256k1Affine as Secp256k1;
+use zkevm_circuits::keccak_circuit::{
+    keccak_bit::KeccakBitCircuit, keccak_packed::KeccakPackedCircuit,
+};
+
+/// Runs `circuit`, laid out by `V1`, through `MockProver` at size `k`
+fn verify_v1<C: Circuit<Fp>>(k: u32, circuit: C, instances: Vec<Vec<Fp>>) {
+    let prover = match MockProver::run(k, &circuit, instances) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+/// Inputs of the Keccak circuits in `benches/floor_planner.rs`
+fn keccak_inputs() -> Vec<Vec<u8>> {
+    vec![
+        vec![],
+        (0u8..1).collect::<Vec<_>>(),
+        (0u8..135).collect::<Vec<_>>(),
+        (0u8..136).collect::<Vec<_>>(),
+        (0u8..200).collect::<Vec<_>>(),
+    ]
+}
+
+#[test]
+fn test_iszero_rows_used() {
+    let circuit = IsZeroCircuit::new(Value::known(Fp::from(2)), 10);
+
+    // Three gates per repeat, the copy constraints take no row of their own
+    assert_eq!(rows_used(&circuit), 30);
+    assert_eq!(rows_used(&circuit.with_floor_planner::<V1>()), 30);
+}
+
+#[test]
+fn test_iszero_chip_rows_used() {
+    let circuit = IsZeroChipCircuit::new(Value::known(Fp::from(2)), 10);
+    assert_eq!(rows_used(&circuit), 10);
+}
+
+#[test]
+fn test_fitted_circuit_rows_used() {
+    let k = 8;
+    let repeats = IsZeroCircuit::<Fp>::repeats_for_k(k);
+    let rows = rows_used(&IsZeroCircuit::new(Value::known(Fp::from(1)), repeats));
+    assert!(rows <= usable_rows::<Fp, IsZeroCircuit<Fp>>(k));
+    assert!(rows + 3 > usable_rows::<Fp, IsZeroCircuit<Fp>>(k));
+}
+
+#[test]
+fn test_v1_circuits_satisfied() {
+    let k = 8;
+    verify_v1(
+        k,
+        IsZeroCircuit::new(
+            Value::known(Fp::from(0)),
+            IsZeroCircuit::<Fp>::repeats_for_k(k),
+        )
+        .with_floor_planner::<V1>(),
+        vec![],
+    );
+
+    let k = 11;
+    verify_v1(
+        k,
+        RangeCircuit::new(
+            RangeCircuit::<Fp>::bench_inputs(),
+            RangeCircuit::<Fp>::bench_repeats_for_k(k),
+        )
+        .with_floor_planner::<V1>(),
+        vec![vec![]],
+    );
+}
+
+// The ECDSA and `zkevm-circuits` Keccak circuits are benched under `V1` as well, at the smallest
+// sizes of `benches/floor_planner.rs`. ECDSA needs 2^18 rows, too many for every `cargo test`, so
+// run it with `cargo test --test layout -- --ignored`
+#[test]
+#[ignore]
+fn test_v1_ecdsa_satisfied() {
+    verify_v1(
+        18,
+        EcdsaVerifyCircuit::<Secp256k1, Fp>::random().with_floor_planner::<V1>(),
+        vec![vec![]],
+    );
+}
+
+#[test]
+fn test_v1_keccak_satisfied() {
+    let k = 8;
+    let mut circuit = KeccakBitCircuit::new(2usize.pow(k));
+    circuit.generate_witness(&keccak_inputs());
+    verify_v1(k, WithFloorPlanner::<_, V1>::new(circuit), vec![]);
+
+    let k = 9;
+    let mut circuit = KeccakPackedCircuit::new(2usize.pow(k));
+    circuit.generate_witness(&keccak_inputs());
+    verify_v1(k, WithFloorPlanner::<_, V1>::new(circuit), vec![]);
+}
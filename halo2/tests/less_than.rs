@@ -0,0 +1,123 @@
+use halo2::{
+    fit::usable_rows,
+    less_than::RepeatedLessThanCircuit,
+    num2bits::{Num2BitsChip, Num2BitsConfig, Num2BitsInstructions},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    halo2curves::bn256::Fr as Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+};
+
+/// Decomposes `value` into `N` bits
+#[derive(Default)]
+struct Num2BitsCircuit<const N: usize> {
+    value: Value<Fp>,
+}
+
+impl<const N: usize> Circuit<Fp> for Num2BitsCircuit<N> {
+    type Config = (Column<Advice>, Num2BitsConfig);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let input = meta.advice_column();
+        meta.enable_equality(input);
+        (input, Num2BitsChip::configure(meta))
+    }
+
+    fn synthesize(
+        &self,
+        (input, config): Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let value = layouter.assign_region(
+            || "load value",
+            |mut region| region.assign_advice(|| "value", input, 0, || self.value),
+        )?;
+        let bits = Num2BitsChip::new(config).num2bits(layouter, &value, N)?;
+        assert_eq!(bits.len(), N);
+        Ok(())
+    }
+}
+
+fn verify<C: Circuit<Fp>>(circuit: &C, instances: Vec<Vec<Fp>>) -> bool {
+    let prover = match MockProver::run(8, circuit, instances) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    prover.verify().is_ok()
+}
+
+#[test]
+fn test_num2bits() {
+    for value in [0, 1, 2, 200, 255] {
+        let circuit = Num2BitsCircuit::<8> {
+            value: Value::known(Fp::from(value)),
+        };
+        assert!(verify(&circuit, vec![]), "{} should fit in 8 bits", value);
+    }
+    let circuit = Num2BitsCircuit::<1> {
+        value: Value::known(Fp::one()),
+    };
+    assert!(verify(&circuit, vec![]));
+}
+
+#[test]
+fn test_num2bits_overflow() {
+    for value in [256, 1000] {
+        let circuit = Num2BitsCircuit::<8> {
+            value: Value::known(Fp::from(value)),
+        };
+        assert!(
+            !verify(&circuit, vec![]),
+            "{} shouldn't fit in 8 bits",
+            value
+        );
+    }
+    let circuit = Num2BitsCircuit::<8> {
+        value: Value::known(-Fp::one()),
+    };
+    assert!(!verify(&circuit, vec![]));
+}
+
+#[test]
+fn test_less_than() {
+    for (a, b) in [(2, 3), (3, 2), (3, 3), (0, 255), (255, 0), (0, 0)] {
+        let circuit = RepeatedLessThanCircuit::<Fp, 8>::new(
+            Value::known(Fp::from(a)),
+            Value::known(Fp::from(b)),
+            3,
+        );
+        let instances = RepeatedLessThanCircuit::<Fp, 8>::instances(a, b);
+        assert!(verify(&circuit, instances), "{} < {}", a, b);
+    }
+}
+
+#[test]
+fn test_less_than_wrong_output() {
+    for (a, b) in [(2, 3), (3, 2), (3, 3)] {
+        let circuit = RepeatedLessThanCircuit::<Fp, 8>::new(
+            Value::known(Fp::from(a)),
+            Value::known(Fp::from(b)),
+            3,
+        );
+        let instances = vec![vec![Fp::from(u64::from(a >= b))]];
+        assert!(!verify(&circuit, instances), "{} < {}", a, b);
+    }
+}
+
+#[test]
+fn test_less_than_fills_rows() {
+    // Every repeat takes the 9 rows of the decomposition of `a + 2^8 - b`
+    let k = 10;
+    let repeats = RepeatedLessThanCircuit::<Fp, 8>::repeats_for_k(k);
+    assert_eq!(
+        repeats,
+        usable_rows::<Fp, RepeatedLessThanCircuit<Fp, 8>>(k) / 9
+    );
+}
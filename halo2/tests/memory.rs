@@ -0,0 +1,29 @@
+use halo2::memory::{measure, CountingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// A single test, as the counters are shared by every thread of the test binary
+#[test]
+fn test_measure_memory() {
+    const SIZE: usize = 1 << 20;
+
+    // The output is still allocated when measured
+    let (retained, memory) = measure(|| vec![1u8; SIZE]);
+    assert_eq!(retained.len(), SIZE);
+    assert!(memory.peak_bytes >= SIZE);
+    assert!(memory.retained_bytes >= SIZE);
+    drop(retained);
+
+    // Freed allocations count towards the peak and the total, not what is retained
+    let (_, memory) = measure(|| {
+        for _ in 0..4 {
+            let buffer = vec![1u8; SIZE];
+            assert_eq!(buffer.len(), SIZE);
+        }
+    });
+    assert!(memory.peak_bytes >= SIZE);
+    assert!(memory.peak_bytes < 2 * SIZE);
+    assert!(memory.allocated_bytes >= 4 * SIZE as u64);
+    assert!(memory.retained_bytes < SIZE);
+}
@@ -0,0 +1,128 @@
+use halo2::{
+    prover::{
+        keygen, prove_many, prove_with, prove_with_transcript, verify_batch, verify_many,
+        verify_with, MultiOpen,
+    },
+    transcript::TranscriptHash,
+    tutorial::TutorialCircuit,
+};
+use halo2_proofs::{
+    circuit::Value,
+    halo2curves::bn256::{Bn256, Fr},
+    poly::{commitment::ParamsProver, kzg::commitment::ParamsKZG},
+};
+
+fn tutorial_circuit() -> TutorialCircuit<Fr> {
+    TutorialCircuit::new(
+        Value::known(Fr::from(5)),
+        Value::known(Fr::from(9)),
+        Fr::from(7),
+    )
+}
+
+#[test]
+fn test_multiopen_schemes() {
+    let params: ParamsKZG<Bn256> = ParamsKZG::new(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances: &[&[&[Fr]]] = &[&[&[Fr::from(7), Fr::from(25 * 81 + 7)]]];
+
+    for scheme in MultiOpen::ALL {
+        let proof = prove_with(scheme, &params, &pk, &[tutorial_circuit()], instances)
+            .expect("proof generation should not fail");
+        verify_with(scheme, &params, pk.get_vk(), instances, &proof)
+            .expect("proof verification should not fail");
+
+        // A proof only verifies under the scheme it was created with
+        for other in MultiOpen::ALL.into_iter().filter(|other| *other != scheme) {
+            assert!(verify_with(other, &params, pk.get_vk(), instances, &proof).is_err());
+        }
+    }
+}
+
+#[test]
+fn test_multiopen_names() {
+    for scheme in MultiOpen::ALL {
+        assert_eq!(MultiOpen::parse(scheme.name()), Some(scheme));
+    }
+    assert_eq!(MultiOpen::parse("shplonk"), Some(MultiOpen::Shplonk));
+    assert_eq!(MultiOpen::parse("kzg"), None);
+}
+
+#[test]
+fn test_batch_verification() {
+    let params: ParamsKZG<Bn256> = ParamsKZG::new(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances: &[&[&[Fr]]] = &[&[&[Fr::from(7), Fr::from(25 * 81 + 7)]]];
+    let wrong_instances: &[&[&[Fr]]] = &[&[&[Fr::from(7), Fr::from(25 * 81 + 8)]]];
+
+    for (scheme, transcript) in MultiOpen::ALL
+        .into_iter()
+        .flat_map(|scheme| TranscriptHash::ALL.map(|transcript| (scheme, transcript)))
+    {
+        let proofs: Vec<Vec<u8>> = (0..3)
+            .map(|_| {
+                prove_with_transcript(
+                    scheme,
+                    transcript,
+                    &params,
+                    &pk,
+                    &[tutorial_circuit()],
+                    instances,
+                )
+                .expect("proof generation should not fail")
+            })
+            .collect();
+        let batch: Vec<(&[&[&[Fr]]], &[u8])> = proofs
+            .iter()
+            .map(|proof| (instances, proof.as_slice()))
+            .collect();
+        verify_batch(scheme, transcript, &params, pk.get_vk(), &batch)
+            .expect("batch verification should not fail");
+
+        // A single invalid proof fails the whole batch
+        let mut invalid = batch.clone();
+        invalid[1].0 = wrong_instances;
+        assert!(verify_batch(scheme, transcript, &params, pk.get_vk(), &invalid).is_err());
+
+        // As does verifying with another transcript than the proofs were made with
+        let other = TranscriptHash::ALL
+            .into_iter()
+            .find(|&other| other != transcript)
+            .expect("there should be several transcripts");
+        assert!(verify_batch(scheme, other, &params, pk.get_vk(), &batch).is_err());
+    }
+}
+
+#[test]
+fn test_multi_instance_proof() {
+    let params: ParamsKZG<Bn256> = ParamsKZG::new(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances = vec![
+        vec![vec![Fr::from(7), Fr::from(25 * 81 + 7)]],
+        vec![vec![Fr::from(7), Fr::from(4 * 9 + 7)]],
+    ];
+
+    for scheme in MultiOpen::ALL {
+        let proof = prove_many(
+            scheme,
+            &params,
+            &pk,
+            &[
+                tutorial_circuit(),
+                TutorialCircuit::new(
+                    Value::known(Fr::from(2)),
+                    Value::known(Fr::from(3)),
+                    Fr::from(7),
+                ),
+            ],
+            &instances,
+        )
+        .expect("proof generation should not fail");
+        verify_many(scheme, &params, pk.get_vk(), &instances, &proof)
+            .expect("proof verification should not fail");
+
+        // Every circuit is checked against its own public inputs
+        let swapped = vec![instances[1].clone(), instances[0].clone()];
+        assert!(verify_many(scheme, &params, pk.get_vk(), &swapped, &proof).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use ff::{Field, PrimeField};
+use group::{prime::PrimeCurveAffine, Curve};
+use halo2::{
+    circom::PtauFile,
+    prover::{keygen, prove, verify},
+    tutorial::TutorialCircuit,
+};
+use halo2_proofs::{
+    arithmetic::CurveAffine,
+    circuit::Value,
+    halo2curves::bn256::{Fq, Fr, G1Affine, G2Affine},
+    poly::commitment::Params,
+};
+use rand_core::OsRng;
+use std::io::Cursor;
+
+/// Coordinate in the little endian Montgomery form of `.ptau` files
+fn montgomery(coordinate: &Fq) -> Vec<u8> {
+    (*coordinate * Fq::from(2).pow_vartime([256]))
+        .to_repr()
+        .as_ref()
+        .to_vec()
+}
+
+fn section(file: &mut Vec<u8>, section_type: u32, content: Vec<u8>) {
+    file.extend_from_slice(&section_type.to_le_bytes());
+    file.extend_from_slice(&(content.len() as u64).to_le_bytes());
+    file.extend(content);
+}
+
+/// Writes a `.ptau` file of the given power, with `tau` in tauG1 and `tau_g2` in tauG2
+fn write_ptau(power: u32, tau: Fr, tau_g2: Fr) -> Vec<u8> {
+    let mut file = b"ptau".to_vec();
+    file.extend_from_slice(&1u32.to_le_bytes());
+    file.extend_from_slice(&3u32.to_le_bytes());
+
+    // Modulus of Fq, i.e. -1 + 1
+    let mut modulus = (-Fq::one()).to_repr().as_ref().to_vec();
+    modulus[0] += 1;
+    let mut content = vec![];
+    content.extend_from_slice(&32u32.to_le_bytes());
+    content.extend(modulus);
+    content.extend_from_slice(&power.to_le_bytes());
+    content.extend_from_slice(&power.to_le_bytes());
+    section(&mut file, 1, content);
+
+    let mut content = vec![];
+    let mut power_of_tau = Fr::one();
+    for _ in 0..(2 << power) - 1 {
+        let point = (G1Affine::generator() * power_of_tau).to_affine();
+        let coordinates = point.coordinates().unwrap();
+        content.extend(montgomery(coordinates.x()));
+        content.extend(montgomery(coordinates.y()));
+        power_of_tau *= tau;
+    }
+    section(&mut file, 2, content);
+
+    let mut content = vec![];
+    let mut power_of_tau = Fr::one();
+    for _ in 0..1 << power {
+        let point = (G2Affine::generator() * power_of_tau).to_affine();
+        let coordinates = point.coordinates().unwrap();
+        for coordinate in [coordinates.x(), coordinates.y()] {
+            content.extend(montgomery(&coordinate.c0));
+            content.extend(montgomery(&coordinate.c1));
+        }
+        power_of_tau *= tau_g2;
+    }
+    section(&mut file, 3, content);
+    file
+}
+
+#[test]
+fn test_ptau_params() {
+    let tau = Fr::random(OsRng);
+    let mut ptau = PtauFile::read(Cursor::new(write_ptau(5, tau, tau))).unwrap();
+    assert_eq!(ptau.power, 5);
+    assert_eq!(ptau.ceremony_power, 5);
+
+    let constant = Fr::from(7);
+    let z = Fr::from(25 * 81 + 7);
+    let circuit = || {
+        TutorialCircuit::new(
+            Value::known(Fr::from(5)),
+            Value::known(Fr::from(9)),
+            constant,
+        )
+    };
+
+    // The tutorial circuit fits in 2^4 rows, and smaller parameters are read from the same file
+    for k in [4, 5] {
+        let params = ptau.params(k).unwrap();
+        assert_eq!(params.k(), k);
+
+        let pk = keygen(&params, &circuit()).unwrap();
+        let instances: &[&[&[Fr]]] = &[&[&[constant, z]]];
+        let proof = prove(&params, &pk, &[circuit()], instances).unwrap();
+        verify(&params, pk.get_vk(), instances, &proof)
+            .expect("proof verification should not fail");
+    }
+
+    assert!(ptau.params(6).is_err());
+}
+
+#[test]
+fn test_ptau_rejects_inconsistent_powers() {
+    let mut ptau = PtauFile::read(Cursor::new(write_ptau(2, Fr::from(3), Fr::from(4)))).unwrap();
+    assert!(ptau.params(2).is_err());
+
+    assert!(PtauFile::read(Cursor::new(b"r1cs".to_vec())).is_err());
+}
@@ -0,0 +1,55 @@
+use halo2::{
+    groth16::circuits::{iszero, repeated_less_than, LESS_THAN_BITS},
+    r1cs_circuit::R1csCircuit,
+};
+use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr as Fp};
+
+fn mock_prove(circuit: &R1csCircuit<Fp>, instances: Vec<Vec<Fp>>) -> bool {
+    let prover = match MockProver::run(circuit.min_k(), circuit, instances) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    prover.verify().is_ok()
+}
+
+#[test]
+fn test_iszero_r1cs_circuit() {
+    for a in [Fp::from(0), Fp::from(2)] {
+        let (r1cs, witness) = iszero(a, 10);
+        let circuit = R1csCircuit::new(r1cs, witness);
+        assert_eq!(circuit.instances()[0].len(), 10);
+        assert!(mock_prove(&circuit, circuit.instances()));
+    }
+}
+
+#[test]
+fn test_less_than_r1cs_circuit() {
+    for (a, b) in [(2, 3), (3, 2), (3, 3)] {
+        let (r1cs, witness) = repeated_less_than(a, b, 5, LESS_THAN_BITS);
+        let circuit = R1csCircuit::new(r1cs, witness);
+        assert!(mock_prove(&circuit, circuit.instances()));
+    }
+}
+
+#[test]
+fn test_r1cs_circuit_rejects_bad_witness() {
+    // Claim that 2 >= 3 in the instance column
+    let (r1cs, mut witness) = repeated_less_than(2, 3, 1, LESS_THAN_BITS);
+    let circuit = R1csCircuit::new(r1cs.clone(), witness.clone());
+    assert!(!mock_prove(&circuit, vec![vec![Fp::from(0)]]));
+
+    // Flip a bit of the decomposition
+    let last = witness.len() - 1;
+    witness[last] = Fp::from(1) - witness[last];
+    let circuit = R1csCircuit::new(r1cs, witness);
+    assert!(!mock_prove(&circuit, circuit.instances()));
+}
+
+#[test]
+fn test_r1cs_circuit_rows() {
+    // Every IsZero repeat takes 3 rows, after the constant one and the public outputs
+    let (r1cs, witness) = iszero(Fp::from(2), 10);
+    let circuit = R1csCircuit::new(r1cs, witness);
+    assert_eq!(circuit.num_rows(), 1 + 10 + 3 * 10);
+    assert_eq!(circuit.min_k(), 6);
+}
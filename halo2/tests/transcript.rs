@@ -0,0 +1,74 @@
+use halo2::{
+    prover::{keygen, prove_with_transcript, verify_with_transcript, MultiOpen},
+    transcript::TranscriptHash,
+    tutorial::TutorialCircuit,
+};
+use halo2_proofs::{
+    circuit::Value,
+    halo2curves::bn256::{Bn256, Fr},
+    poly::{commitment::ParamsProver, kzg::commitment::ParamsKZG},
+};
+
+fn tutorial_circuit() -> TutorialCircuit<Fr> {
+    TutorialCircuit::new(
+        Value::known(Fr::from(5)),
+        Value::known(Fr::from(9)),
+        Fr::from(7),
+    )
+}
+
+#[test]
+fn test_transcripts() {
+    let params: ParamsKZG<Bn256> = ParamsKZG::new(4);
+    let pk = keygen(&params, &tutorial_circuit()).expect("keygen should not fail");
+    let instances: &[&[&[Fr]]] = &[&[&[Fr::from(7), Fr::from(25 * 81 + 7)]]];
+
+    for scheme in MultiOpen::ALL {
+        let mut proof_sizes = vec![];
+        for transcript in TranscriptHash::ALL {
+            let proof = prove_with_transcript(
+                scheme,
+                transcript,
+                &params,
+                &pk,
+                &[tutorial_circuit()],
+                instances,
+            )
+            .expect("proof generation should not fail");
+            verify_with_transcript(scheme, transcript, &params, pk.get_vk(), instances, &proof)
+                .expect("proof verification should not fail");
+            proof_sizes.push(proof.len());
+
+            // A proof only verifies with the transcript it was created with
+            for other in TranscriptHash::ALL
+                .into_iter()
+                .filter(|other| *other != transcript)
+            {
+                assert!(verify_with_transcript(
+                    scheme,
+                    other,
+                    &params,
+                    pk.get_vk(),
+                    instances,
+                    &proof
+                )
+                .is_err());
+            }
+        }
+
+        // The transcript only changes the challenges, not what the proof holds
+        assert!(proof_sizes.iter().all(|size| *size == proof_sizes[0]));
+    }
+}
+
+#[test]
+fn test_transcript_names() {
+    for transcript in TranscriptHash::ALL {
+        assert_eq!(TranscriptHash::parse(transcript.name()), Some(transcript));
+    }
+    assert_eq!(
+        TranscriptHash::parse("keccak256"),
+        Some(TranscriptHash::Keccak256)
+    );
+    assert_eq!(TranscriptHash::parse("sha256"), None);
+}